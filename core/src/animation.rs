@@ -0,0 +1,652 @@
+use super::framebuffer;
+use super::glyph::Glyph;
+use super::hal;
+
+const WIN_ANIMATION_MAX_LED_OUTER_STEP: u8 = 5; // max led_step
+const WIN_ANIMATION_MAX_LED_INNER_STEP: u8 = 10; // multiplied by 256 internally // add led_quarter
+const WIN_ANIMATION_TIME_LED_STEP: u8 = 2; // from this led_step on the time is shown
+const WIN_ANIMATION_ATTEMPTS_LED_STEP: u8 = 3; // from this led_step on the attempts are shown
+const WIN_ANIMATION_TAIL_LED: framebuffer::Led = framebuffer::Led {
+    intensity: 2,
+    blink: framebuffer::Blink::Steady,
+};
+const MAX_MINUTE_DIGITS: usize = 2;
+// The dot between the minutes and the seconds of MM.SS.
+pub const TIME_DOTS: u8 = 1 << 2;
+const ATTEMPTS_LABEL: Glyph = Glyph::const_ascii(b't');
+const BEST_ATTEMPTS_LABEL: Glyph = Glyph::const_ascii(b'b');
+const NO_VALUE: Glyph = Glyph::DASH;
+
+const HELO_ANIMATION_MAX_INNER_STEP: u8 = 20; // multiplied by 256 internally
+const HELO_ANIMATION_MAX_OUTER_STEP: u8 = 5;
+const HELO_TEXT: &str = "HELO";
+
+// Steps of the game for a shift of the text by one digit,
+// a quarter of a second on the board.
+pub const MARQUEE_STEPS_PER_SHIFT: u16 = 2500;
+const NO_TEXT: &str = "";
+
+const GUESS_ANIMATION_MAX_STEP: u8 = 12; // multiplied by 256 internally
+const LOSE_ANIMATION_PERIOD_STEP: u8 = 12; // multiplied by 256 internally, a digit revealed or a blink
+const LOSE_ANIMATION_BLINKS: u8 = 6; // hides and shows of the secret, even so it ends shown
+const DIGIT_INCREMENT_ANIMATION_MAX_STEP: u8 = 4; // multiplied by 256 internally
+
+#[derive(PartialEq, Eq)]
+pub enum AnimationState {
+    Running,
+    End
+}
+
+pub trait Animation {
+    fn step(&mut self, seven_segment: &mut dyn hal::SevenSegmentDisplay, led_matrix: &mut dyn hal::LEDMatrixDisplay) -> AnimationState;
+    fn cleanup(&mut self, seven_segment: &mut dyn hal::SevenSegmentDisplay, led_matrix: &mut dyn hal::LEDMatrixDisplay);
+    fn running(&self) -> bool;
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum AnimationKind {
+    Hello,
+    Win,
+    Guess,
+    DigitIncrement,
+    Marquee,
+    Lose,
+}
+
+pub struct Animations<const D: usize> {
+    pub hello: HelloAnimation,
+    pub win: WinAnimation<D>,
+    pub guess: GuessAnimation,
+    pub digit_increment: DigitIncrementAnimation,
+    pub marquee: MarqueeAnimation,
+    pub lose: LoseAnimation<D>,
+}
+
+impl<const D: usize> Animations<D> {
+    pub fn create() -> Animations<D> {
+        Animations {
+            hello: HelloAnimation::create(),
+            win: WinAnimation::create([0; D], D, 0, 0),
+            guess: GuessAnimation::create(),
+            digit_increment: DigitIncrementAnimation::create(0),
+            marquee: MarqueeAnimation::create(&NO_TEXT, MARQUEE_STEPS_PER_SHIFT),
+            lose: LoseAnimation::create([0; D], D),
+        }
+    }
+
+    pub fn get(&mut self, kind: AnimationKind) -> &mut dyn Animation {
+        match kind {
+            AnimationKind::Hello => &mut self.hello,
+            AnimationKind::Win => &mut self.win,
+            AnimationKind::Guess => &mut self.guess,
+            AnimationKind::DigitIncrement => &mut self.digit_increment,
+            AnimationKind::Marquee => &mut self.marquee,
+            AnimationKind::Lose => &mut self.lose,
+        }
+    }
+}
+
+pub struct HelloAnimation {
+    pub inner_step: u8,
+    pub outer_step: u8,
+    pub hidden: bool,
+    pub internal_step: u8,
+}
+
+pub struct WinAnimation<const D: usize> {
+    pub number: [u8; D],
+    pub length: usize,
+    pub attempts: u16,
+    pub time: u32,
+    pub showing_time: bool,
+    pub showing_attempts: bool,
+    pub led_step: u8,
+    pub led_quarter: u8,
+    pub led_inner: u8,
+    pub hidden: bool,
+    pub internal_step: u8,
+}
+
+// The secret of a lost game is revealed digit by digit from the left,
+// hidden by dashes until then, while the LEDs go out column by column.
+// Then the secret blinks.
+pub struct LoseAnimation<const D: usize> {
+    pub number: [u8; D],
+    pub length: usize,
+    pub period: u8, // the dashes, a period for each digit, then the blinks
+    pub step: u8,
+    pub hidden: bool,
+    pub internal_step: u8,
+}
+
+// The text comes in from the right and scrolls out to the left,
+// each byte takes a digit, the characters not in the font
+// are shown as Glyph::FALLBACK.
+pub struct MarqueeAnimation {
+    pub text: &'static dyn hal::Text,
+    pub steps_per_shift: u16,
+    pub shift: usize,
+    pub shifts: usize, // until the text is out, set on the first step
+    pub internal_step: u16,
+}
+
+pub struct GuessAnimation {
+    pub step: u8,
+    pub internal_step: u8,
+}
+
+pub struct DigitIncrementAnimation {
+    pub digit_index: usize,
+    pub step: u8,
+    pub internal_step: u8,
+}
+
+impl DigitIncrementAnimation {
+    pub fn create(digit_index: usize) -> DigitIncrementAnimation {
+        DigitIncrementAnimation {
+            step: 0,
+            internal_step: 0,
+            digit_index
+        }
+    }
+
+    pub fn reset(&mut self, digit_index: usize) {
+        self.digit_index = digit_index;
+        self.step = 0;
+    }
+}
+
+impl Animation for DigitIncrementAnimation {
+    fn step(&mut self, seven_segment: &mut dyn hal::SevenSegmentDisplay, _: &mut dyn hal::LEDMatrixDisplay) -> AnimationState {
+        if !self.running() {
+            return AnimationState::End;
+        }
+
+        if self.step == 0 {
+            seven_segment.hide_digit(self.digit_index);
+        }
+
+        self.internal_step += 1;
+        if self.internal_step == 255 {
+            self.step += 1;
+            self.internal_step = 0;
+        }
+
+        AnimationState::Running
+    }
+
+    fn cleanup(&mut self, seven_segment: &mut dyn hal::SevenSegmentDisplay, _: &mut dyn hal::LEDMatrixDisplay) {
+        seven_segment.show_digit(self.digit_index);
+    }
+
+    fn running(&self) -> bool {
+        self.step < DIGIT_INCREMENT_ANIMATION_MAX_STEP
+    }
+}
+
+impl MarqueeAnimation {
+    pub fn create(text: &'static dyn hal::Text, steps_per_shift: u16) -> MarqueeAnimation {
+        MarqueeAnimation {
+            text,
+            steps_per_shift: steps_per_shift.max(1),
+            shift: 0,
+            shifts: 0,
+            internal_step: 0
+        }
+    }
+
+    pub fn reset(&mut self, text: &'static dyn hal::Text, steps_per_shift: u16) {
+        self.text = text;
+        self.steps_per_shift = steps_per_shift.max(1);
+        self.shift = 0;
+        self.internal_step = 0;
+    }
+
+    // After the first shift the first byte is on the rightmost digit.
+    fn show(&self, seven_segment: &mut dyn hal::SevenSegmentDisplay) {
+        for digit_index in 0..seven_segment.digits() {
+            let glyph = (self.shift - 1)
+                .checked_sub(digit_index)
+                .filter(|position| *position < self.text.len())
+                .map(|position| Glyph::ascii(self.text.byte(position)));
+            seven_segment.set_glyph(digit_index, glyph);
+        }
+    }
+}
+
+impl Animation for MarqueeAnimation {
+    fn step(&mut self, seven_segment: &mut dyn hal::SevenSegmentDisplay, led_matrix: &mut dyn hal::LEDMatrixDisplay) -> AnimationState {
+        if self.shift == 0 {
+            seven_segment.clear();
+            led_matrix.clear();
+            self.shifts = self.text.len() + seven_segment.digits();
+        }
+
+        if self.internal_step == 0 {
+            self.shift += 1;
+            if self.shift >= self.shifts {
+                return AnimationState::End;
+            }
+            self.show(seven_segment);
+        }
+
+        self.internal_step += 1;
+        if self.internal_step >= self.steps_per_shift {
+            self.internal_step = 0;
+        }
+        AnimationState::Running
+    }
+
+    fn cleanup(&mut self, seven_segment: &mut dyn hal::SevenSegmentDisplay, _: &mut dyn hal::LEDMatrixDisplay) {
+        seven_segment.clear();
+        seven_segment.show_all_digits();
+    }
+
+    fn running(&self) -> bool {
+        self.shift == 0 || self.shift < self.shifts
+    }
+}
+
+impl<const D: usize> LoseAnimation<D> {
+    pub fn create(number: [u8; D], length: usize) -> LoseAnimation<D> {
+        LoseAnimation {
+            number,
+            length,
+            period: 0,
+            step: 0,
+            hidden: false,
+            internal_step: 0
+        }
+    }
+
+    pub fn reset(&mut self, number: [u8; D], length: usize) {
+        self.number = number;
+        self.length = length;
+        self.period = 0;
+        self.step = 0;
+        self.hidden = false;
+        self.internal_step = 0;
+    }
+
+    fn start_period(&mut self, seven_segment: &mut dyn hal::SevenSegmentDisplay, led_matrix: &mut dyn hal::LEDMatrixDisplay) {
+        let period = usize::from(self.period);
+
+        if period == 0 {
+            seven_segment.set_dots(0);
+            for digit_index in 0..D {
+                let glyph = if digit_index < self.length { Some(NO_VALUE) } else { None };
+                seven_segment.set_glyph(digit_index, glyph);
+            }
+            led_matrix.fill();
+        } else if period <= self.length {
+            // the digits from the left, the LEDs of the revealed ones go out
+            let digit_index = self.length - period;
+            seven_segment.set_digit(digit_index, Some(self.number[digit_index]));
+
+            led_matrix.clear();
+            for x in 0..digit_index as u8 {
+                for y in 0..led_matrix.height() {
+                    led_matrix.set(x, y);
+                }
+            }
+        } else {
+            if self.hidden {
+                seven_segment.show_all_digits();
+            } else {
+                seven_segment.hide_all_digits();
+            }
+            self.hidden = !self.hidden;
+        }
+    }
+}
+
+impl<const D: usize> Animation for LoseAnimation<D> {
+    fn step(&mut self, seven_segment: &mut dyn hal::SevenSegmentDisplay, led_matrix: &mut dyn hal::LEDMatrixDisplay) -> AnimationState {
+        if !self.running() {
+            return AnimationState::End;
+        }
+
+        if self.step == 0 && self.internal_step == 0 {
+            self.start_period(seven_segment, led_matrix);
+        }
+
+        self.internal_step += 1;
+        if self.internal_step == 255 {
+            self.step += 1;
+            self.internal_step = 0;
+        }
+
+        if self.step == LOSE_ANIMATION_PERIOD_STEP {
+            self.step = 0;
+            self.period += 1;
+        }
+
+        AnimationState::Running
+    }
+
+    // Ends on the secret, however far the animation got.
+    fn cleanup(&mut self, seven_segment: &mut dyn hal::SevenSegmentDisplay, led_matrix: &mut dyn hal::LEDMatrixDisplay) {
+        seven_segment.set_number(&self.number);
+        seven_segment.set_dots(0);
+        for digit_index in self.length..D {
+            seven_segment.set_digit(digit_index, None);
+        }
+        seven_segment.show_all_digits();
+        led_matrix.clear();
+    }
+
+    fn running(&self) -> bool {
+        usize::from(self.period) <= self.length + usize::from(LOSE_ANIMATION_BLINKS)
+    }
+}
+
+impl GuessAnimation {
+    pub fn create() -> GuessAnimation {
+        GuessAnimation {
+            step: 0,
+            internal_step: 0
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.step = 0;
+    }
+}
+
+impl Animation for GuessAnimation {
+    fn step(&mut self, seven_segment: &mut dyn hal::SevenSegmentDisplay, _: &mut dyn hal::LEDMatrixDisplay) -> AnimationState {
+        if !self.running() {
+            return AnimationState::End;
+        }
+
+        if self.step == 0 {
+            seven_segment.hide_all_digits();
+        }
+
+        self.internal_step += 1;
+        if self.internal_step == 255 {
+            self.step += 1;
+            self.internal_step = 0;
+        }
+
+        AnimationState::Running
+    }
+
+    fn cleanup(&mut self, seven_segment: &mut dyn hal::SevenSegmentDisplay, _: &mut dyn hal::LEDMatrixDisplay) {
+        seven_segment.show_all_digits();
+    }
+
+    fn running(&self) -> bool {
+        self.step < GUESS_ANIMATION_MAX_STEP
+    }
+}
+
+// The largest value of the digits, 10^digits - 1.
+fn max_value(digits: usize) -> u32 {
+    let mut max = 1;
+    for _ in 0..digits {
+        max *= 10;
+    }
+
+    max - 1
+}
+
+// The label on the leftmost digit, the value right aligned,
+// dashes without a value. Values too long for the digits after
+// the label take all the digits. D is at least 2, as the game checks.
+pub fn labeled_digits<const D: usize>(label: Glyph, value: Option<u32>) -> [Option<Glyph>; D] {
+    let mut digits = [Some(NO_VALUE); D];
+    digits[D - 1] = Some(label);
+
+    if let Some(value) = value {
+        let mut value = value.min(max_value(D));
+        let shown = if value > max_value(D - 1) { D } else { D - 1 };
+        digits[..shown].fill(None);
+
+        for digit in digits[..shown].iter_mut() {
+            *digit = Some(Glyph::digit((value % 10) as u8));
+            value /= 10;
+            if value == 0 {
+                break;
+            }
+        }
+    }
+
+    digits
+}
+
+// Attempts up to the longest value shown after the label.
+#[inline]
+fn shown_attempts<const D: usize>(attempts: u16) -> u32 {
+    u32::from(attempts).min(max_value(D - 1))
+}
+
+// Attempts of the last game as "t  7".
+#[inline]
+pub fn attempts_digits<const D: usize>(attempts: u16) -> [Option<Glyph>; D] {
+    labeled_digits(ATTEMPTS_LABEL, Some(shown_attempts::<D>(attempts)))
+}
+
+// Best attempts as "b  7", "b---" when no game was won yet.
+#[inline]
+pub fn best_attempts_digits<const D: usize>(best_attempts: Option<u16>) -> [Option<Glyph>; D] {
+    labeled_digits(BEST_ATTEMPTS_LABEL, best_attempts.map(shown_attempts::<D>))
+}
+
+// Milliseconds as MM.SS, shown with TIME_DOTS, the leftmost digits
+// are left blank. Three digits show M.SS. Dashes without a time,
+// longer times show 99.59 or 9.59. D is at least 2, as the game checks.
+pub fn time_digits<const D: usize>(time: Option<u32>) -> [Option<Glyph>; D] {
+    let time = match time {
+        Some(time) => time,
+        None => return [Some(NO_VALUE); D],
+    };
+
+    let minute_digits = (D - 2).min(MAX_MINUTE_DIGITS);
+    let max_minutes = max_value(minute_digits);
+
+    let mut seconds = time / 1000;
+    let mut minutes = seconds / 60;
+    seconds %= 60;
+    if minutes > max_minutes {
+        minutes = max_minutes;
+        seconds = 59;
+    }
+
+    let mut digits = [None; D];
+    digits[0] = Some(Glyph::digit((seconds % 10) as u8));
+    digits[1] = Some(Glyph::digit((seconds / 10) as u8));
+    for digit in digits[2..2 + minute_digits].iter_mut() {
+        *digit = Some(Glyph::digit((minutes % 10) as u8));
+        minutes /= 10;
+    }
+
+    digits
+}
+
+impl<const D: usize> WinAnimation<D> {
+    pub fn create(number: [u8; D], length: usize, attempts: u16, time: u32) -> WinAnimation<D> {
+        WinAnimation {
+            number,
+            length,
+            attempts,
+            time,
+            showing_time: false,
+            showing_attempts: false,
+            led_inner: 0,
+            led_quarter: 0,
+            led_step: 0,
+            hidden: true,
+            internal_step: 0
+        }
+    }
+
+    pub fn reset(&mut self, number: [u8; D], length: usize, attempts: u16, time: u32) {
+        self.number = number;
+        self.length = length;
+        self.attempts = attempts;
+        self.time = time;
+        self.showing_time = false;
+        self.showing_attempts = false;
+        self.led_step = 0;
+        self.led_quarter = 0;
+        self.led_inner = 0;
+        self.hidden = true;
+    }
+}
+
+impl<const D: usize> Animation for WinAnimation<D> {
+    fn step(&mut self, seven_segment: &mut dyn hal::SevenSegmentDisplay, led_matrix: &mut dyn hal::LEDMatrixDisplay) -> AnimationState {
+        if self.led_inner == 0 && self.led_quarter == 0 && self.led_step == 0 {
+            seven_segment.set_number(&self.number);
+            seven_segment.set_dots(0);
+            for i in self.length..self.number.len() {
+                seven_segment.set_digit(i, None);
+            }
+            led_matrix.clear();
+        }
+
+        // the secret first, then the time, then the attempts
+        if self.led_step >= WIN_ANIMATION_TIME_LED_STEP && !self.showing_time {
+            seven_segment.set_glyphs(&time_digits::<D>(Some(self.time)));
+            seven_segment.set_dots(TIME_DOTS);
+            self.showing_time = true;
+        }
+
+        if self.led_step >= WIN_ANIMATION_ATTEMPTS_LED_STEP && !self.showing_attempts {
+            seven_segment.set_glyphs(&attempts_digits::<D>(self.attempts));
+            seven_segment.set_dots(0);
+            self.showing_attempts = true;
+        }
+
+        if self.led_inner > WIN_ANIMATION_MAX_LED_INNER_STEP {
+            self.led_inner = 0;
+            self.led_quarter += 1;
+        }
+
+        // a quarter is a column of the matrix
+        let width = led_matrix.width();
+        if self.led_quarter >= width {
+            self.led_quarter = 0;
+            self.led_step += 1;
+        }
+
+        if self.led_step > WIN_ANIMATION_MAX_LED_OUTER_STEP {
+            return AnimationState::End;
+        }
+
+        led_matrix.clear();
+
+        if self.led_step < 2 {
+            led_matrix.set(self.led_quarter, self.led_step);
+            // a dimmer tail behind the running LED
+            if self.led_quarter > 0 {
+                led_matrix.set_led(self.led_quarter - 1, self.led_step, WIN_ANIMATION_TAIL_LED);
+            }
+        } else if self.led_step == 2 {
+            led_matrix.set(self.led_quarter, 0);
+            led_matrix.set(self.led_quarter, 1);
+        } else if self.led_step == 3 {
+            led_matrix.set(width - 1 - self.led_quarter, 0);
+            led_matrix.set(width - 1 - self.led_quarter, 1);
+        } else {
+            for i in 0..=self.led_quarter {
+                led_matrix.set(i, 0);
+                led_matrix.set(i, 1);
+            }
+        }
+
+        if (self.led_quarter == 2 || self.led_quarter == 0) && self.led_inner == 0 {
+            if self.hidden {
+                seven_segment.show_all_digits();
+            } else {
+                seven_segment.hide_all_digits();
+            }
+
+            self.hidden = !self.hidden;
+        }
+
+        self.internal_step += 1;
+        if self.internal_step == 255 {
+            self.led_inner += 1;
+            self.internal_step = 0;
+        }
+        AnimationState::Running
+    }
+
+    fn cleanup(&mut self, seven_segment: &mut dyn hal::SevenSegmentDisplay, led_matrix: &mut dyn hal::LEDMatrixDisplay) {
+        led_matrix.fill();
+        seven_segment.show_all_digits();
+    }
+
+    #[inline]
+    fn running(&self) -> bool {
+        self.led_step < WIN_ANIMATION_MAX_LED_OUTER_STEP
+    }
+}
+
+impl HelloAnimation {
+    pub fn create() -> HelloAnimation {
+        HelloAnimation {
+            inner_step: 0,
+            outer_step: 0,
+            internal_step: 0,
+            hidden: false
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.inner_step = 0;
+        self.outer_step = 0;
+        self.internal_step = 0;
+        self.hidden = false;
+    }
+}
+
+impl Animation for HelloAnimation {
+
+    fn step(&mut self, seven_segment: &mut dyn hal::SevenSegmentDisplay, led_matrix: &mut dyn hal::LEDMatrixDisplay) -> AnimationState {
+        // Helo text
+        if self.inner_step == 0 && self.outer_step == 0 {
+            // from the leftmost digit, cut on a shorter display
+            seven_segment.display_str(HELO_TEXT);
+            led_matrix.fill();
+        }
+
+        if self.inner_step >= HELO_ANIMATION_MAX_INNER_STEP {
+            self.inner_step = 0;
+            self.outer_step += 1;
+
+            // the LEDs blink with the text
+            if self.hidden {
+                seven_segment.show_all_digits();
+                led_matrix.fill();
+            } else {
+                seven_segment.hide_all_digits();
+                led_matrix.clear();
+            }
+            self.hidden = !self.hidden;
+        }
+
+        if self.outer_step == HELO_ANIMATION_MAX_OUTER_STEP {
+            return AnimationState::End
+        }
+
+        self.internal_step += 1;
+        if self.internal_step == 255 {
+            self.inner_step += 1;
+            self.internal_step = 0;
+        }
+        AnimationState::Running
+    }
+
+    fn cleanup(&mut self, seven_segment: &mut dyn hal::SevenSegmentDisplay, led_matrix: &mut dyn hal::LEDMatrixDisplay) {
+        led_matrix.clear();
+        seven_segment.show_all_digits();
+    }
+
+    #[inline]
+    fn running(&self) -> bool {
+        self.outer_step < HELO_ANIMATION_MAX_OUTER_STEP
+    }
+}
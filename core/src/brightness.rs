@@ -0,0 +1,85 @@
+// Brightness of the displays, done by blanking in the scan.
+// A slot of the scan (a digit, a row of the matrix) takes SLOT_TICKS
+// ticks of the timer, the display is lit only for the start of it.
+// The eye is more sensitive in the dark, so the lit ticks grow faster
+// than the levels.
+
+pub const LEVELS: u8 = 8;
+pub const MAX_LEVEL: u8 = LEVELS - 1;
+pub const SLOT_TICKS: u8 = 16;
+
+const LIT_TICKS: [u8; LEVELS as usize] = [1, 2, 3, 4, 6, 8, 11, 16];
+
+// Ticks of a slot the display is lit on the level,
+// levels over MAX_LEVEL are taken as MAX_LEVEL.
+#[inline]
+pub fn lit_ticks(level: u8) -> u8 {
+    LIT_TICKS[usize::from(level.min(MAX_LEVEL))]
+}
+
+// Ticks of a slot a single LED of the intensity is lit
+// on the display of the level, at least one.
+pub fn scaled_lit_ticks(level: u8, intensity: u8) -> u8 {
+    let ticks = u16::from(lit_ticks(level)) * u16::from(lit_ticks(intensity)) / u16::from(SLOT_TICKS);
+    (ticks as u8).max(1)
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Tick {
+    Light, // a new slot starts, show it
+    Blank, // the lit part of the slot is over
+    Hold,
+}
+
+pub struct Blanking {
+    lit_ticks: u8,
+    tick: u8,
+}
+
+impl Blanking {
+    pub fn create() -> Blanking {
+        Blanking {
+            lit_ticks: SLOT_TICKS,
+            tick: 0,
+        }
+    }
+
+    // Levels over MAX_LEVEL are taken as MAX_LEVEL.
+    // Takes effect from the next slot at the latest.
+    #[inline]
+    pub fn set_level(&mut self, level: u8) {
+        self.lit_ticks = lit_ticks(level);
+    }
+
+    // The tick of the slot the next step runs.
+    #[inline]
+    pub fn tick(&self) -> u8 {
+        self.tick
+    }
+
+    pub fn step(&mut self) -> Tick {
+        let tick = self.tick;
+        self.tick = (tick + 1) % SLOT_TICKS;
+
+        if tick == 0 {
+            return Tick::Light;
+        }
+
+        if tick == self.lit_ticks {
+            return Tick::Blank;
+        }
+
+        return Tick::Hold;
+    }
+
+    // The last step was the last tick of the slot.
+    #[inline]
+    pub fn slot_ended(&self) -> bool {
+        self.tick == 0
+    }
+
+    #[inline]
+    pub fn reset(&mut self) {
+        self.tick = 0;
+    }
+}
@@ -1,4 +1,4 @@
-use atmega_hal::port::{Pin, mode};
+use super::hal;
 
 #[derive(PartialEq, Eq)]
 pub enum ButtonState {
@@ -8,7 +8,7 @@ pub enum ButtonState {
     Released // The button was just released
 }
 
-#[derive(Copy, Clone, PartialEq, Eq)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum ButtonEvent {
     Click,
     LongClick,
@@ -17,16 +17,20 @@ pub enum ButtonEvent {
     None
 }
 
-const DEBOUNCECYCLES: u8 = 50;
+// The times are in steps, the game loop steps the buttons
+// 10000 times a second on the board.
+pub const DEBOUNCECYCLES: u8 = 50;
 
 const PRESSED_FOR_MAX: u16 = 65000;
 const RELEASED_FOR_MAX: u16 = 65000;
 
-const PRESSED_FOR_LONG: u16 = 1000;
-const RELEASED_FOR_DOUBLE_CLICK: u16 = 1000;
+pub const PRESSED_FOR_LONG: u16 = 6000;
+// LongClickContinuous repeats this often after the long click
+pub const PRESSED_FOR_REPEAT: u16 = 1500;
+pub const RELEASED_FOR_DOUBLE_CLICK: u16 = 3000;
 
-pub struct Button {
-    input: Pin<mode::Input>,
+pub struct Button<I> {
+    input: I,
     active_high: bool,
     last_active: bool,
     active: bool,
@@ -38,8 +42,8 @@ pub struct Button {
     last_event: ButtonEvent,
 }
 
-impl Button {
-    pub fn create(input: Pin<mode::Input>, active_high: bool) -> Button {
+impl<I: hal::InputPin> Button<I> {
+    pub fn create(input: I, active_high: bool) -> Button<I> {
         Button {
             input,
             active_high,
@@ -47,7 +51,8 @@ impl Button {
             active: false,
             integrator: 0,
             pressed_for: 0,
-            released_for: 0,
+            // so the first press is not a double click
+            released_for: RELEASED_FOR_MAX,
             last_event: ButtonEvent::None
         }
     }
@@ -78,6 +83,10 @@ impl Button {
         if self.active {
             if self.pressed_for < PRESSED_FOR_MAX {
                 self.pressed_for += 1;
+            } else {
+                // keeps repeating however long it is held,
+                // back by whole repeats so they stay evenly spaced
+                self.pressed_for = PRESSED_FOR_LONG + (self.pressed_for - PRESSED_FOR_LONG) % PRESSED_FOR_REPEAT + 1;
             }
         }
         else {
@@ -99,7 +108,9 @@ impl Button {
         if self.active && self.pressed_for == PRESSED_FOR_LONG {
             self.last_event = ButtonEvent::LongClick;
         }
-        else if self.active && self.pressed_for % PRESSED_FOR_LONG == 0 && self.last_event == ButtonEvent::None {
+        else if self.active && self.pressed_for > PRESSED_FOR_LONG
+            && (self.pressed_for - PRESSED_FOR_LONG) % PRESSED_FOR_REPEAT == 0
+            && self.last_event == ButtonEvent::None {
             self.last_event = ButtonEvent::LongClickContinuous;
         }
 
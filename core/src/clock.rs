@@ -0,0 +1,52 @@
+// Time in milliseconds, the firmware counts them in a timer interrupt.
+// The counter wraps after about 49 days, differences are taken wrapping.
+
+pub trait Clock {
+    fn millis(&self) -> u32;
+}
+
+pub struct Stopwatch {
+    started: u32,
+    stopped: Option<u32>,
+    running: bool,
+}
+
+impl Stopwatch {
+    pub fn create() -> Stopwatch {
+        Stopwatch {
+            started: 0,
+            stopped: None,
+            running: false,
+        }
+    }
+
+    pub fn start(&mut self, now: u32) {
+        self.started = now;
+        self.stopped = None;
+        self.running = true;
+    }
+
+    // Returns the measured time.
+    pub fn stop(&mut self, now: u32) -> u32 {
+        if self.running {
+            self.stopped = Some(now);
+            self.running = false;
+        }
+
+        self.elapsed(now)
+    }
+
+    #[inline]
+    pub fn running(&self) -> bool {
+        self.running
+    }
+
+    // Time from the start until now, or until the stop.
+    pub fn elapsed(&self, now: u32) -> u32 {
+        if !self.running && self.stopped.is_none() {
+            return 0;
+        }
+
+        self.stopped.unwrap_or(now).wrapping_sub(self.started)
+    }
+}
@@ -0,0 +1,50 @@
+// Collection of entropy for seeding the random number generator.
+// The sources are hardware specific, the firmware implements them,
+// the mixing here is deterministic so it can be tested on the host.
+
+pub trait EntropySource {
+    // A reading whose lower bits are noisy.
+    fn sample(&mut self) -> u16;
+}
+
+pub struct EntropyPool {
+    state: u32,
+}
+
+impl EntropyPool {
+    pub fn create() -> EntropyPool {
+        EntropyPool {
+            state: 0
+        }
+    }
+
+    pub fn mix(&mut self, value: u16) {
+        self.state = spread(self.state.rotate_left(7) ^ u32::from(value));
+    }
+
+    pub fn collect<S: EntropySource>(&mut self, source: &mut S, samples: u8) {
+        for _ in 0..samples {
+            self.mix(source.sample());
+        }
+    }
+
+    // Four bytes to mix into the generator, see rng::Rng::mix.
+    #[inline]
+    pub fn seed(&self) -> [u8; 4] {
+        self.state.to_le_bytes()
+    }
+}
+
+// Finalizer of MurmurHash3, every input bit
+// affects every output bit. It is a bijection,
+// so no entropy is lost.
+pub fn spread(value: u32) -> u32 {
+    let mut h = value;
+    h ^= h >> 16;
+    h = h.wrapping_mul(0x85EB_CA6B);
+    h ^= h >> 13;
+    h = h.wrapping_mul(0xC2B2_AE35);
+    h ^= h >> 16;
+
+    h
+}
@@ -0,0 +1,153 @@
+use super::brightness;
+use super::seven_segment;
+use super::filled_sipo;
+use super::framebuffer;
+use super::glyph::Glyph;
+use super::hal;
+
+const DOT_SEGMENT: u8 = 0b10000000;
+
+// D digits behind N chained shift registers.
+pub struct FilledSevenSegment<O, const D: usize, const N: usize> {
+    seven_segment: seven_segment::SevenSegment<D>,
+    sipo: filled_sipo::FilledSipo<O, N>,
+    frame: framebuffer::SevenSegmentFrame<D>,
+    blanking: brightness::Blanking,
+    update_step: usize
+}
+
+impl<O: hal::OutputPin, const D: usize, const N: usize> FilledSevenSegment<O, D, N> {
+    // The selectors and the segments have to fit the registers,
+    // evaluated by create, so other counts do not compile.
+    const REGISTERS_FIT: () = assert!(D + 8 <= 8 * N && N <= 4, "the digits do not fit the shift registers");
+
+    pub fn create(seven_segment: seven_segment::SevenSegment<D>, sipo: filled_sipo::FilledSipo<O, N>) -> FilledSevenSegment<O, D, N> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::REGISTERS_FIT;
+
+        FilledSevenSegment {
+            seven_segment,
+            sipo,
+            frame: framebuffer::SevenSegmentFrame::create(),
+            blanking: brightness::Blanking::create(),
+            update_step: 0
+        }
+    }
+
+    // Shown from the next digit on.
+    #[inline]
+    pub fn set_frame(&mut self, frame: framebuffer::SevenSegmentFrame<D>) {
+        self.frame = frame;
+        self.blanking.set_level(frame.brightness());
+    }
+
+    #[inline]
+    pub fn show_number_block(&mut self) {
+        while !self.step() {}
+    }
+
+    fn fill_digit(&mut self, digit_index: usize) {
+        if digit_index >= D {
+            return
+        }
+
+        if self.frame.hidden(digit_index) {
+            self.sipo.clear();
+            return
+        }
+
+        let mut segments = match self.frame.glyph(digit_index) {
+            Some(glyph) => glyph.segments(),
+            None => 0,
+        };
+        if self.frame.dot(digit_index) {
+            segments |= DOT_SEGMENT;
+        }
+
+        if segments != 0 {
+            self.seven_segment.fill_segments(&mut self.sipo, segments, digit_index);
+        } else {
+            self.sipo.clear();
+        }
+    }
+
+    // A tick of the scan, every digit is shown for brightness::SLOT_TICKS ticks.
+    // The digit is pushed whole at the start of its slot and blanked
+    // once the lit part is over. Returns true after the last digit.
+    pub fn step(&mut self) -> bool {
+        match self.blanking.step() {
+            brightness::Tick::Light => {
+                self.fill_digit(self.update_step);
+                self.sipo.push_block();
+            }
+            brightness::Tick::Blank => self.sipo.blank(),
+            brightness::Tick::Hold => {}
+        }
+
+        if !self.blanking.slot_ended() {
+            return false;
+        }
+
+        self.update_step += 1;
+        if self.update_step >= D {
+            self.update_step = 0;
+            return true;
+        }
+
+        return false;
+    }
+
+    #[inline]
+    pub fn reset(&mut self) {
+        self.update_step = 0;
+        self.blanking.reset();
+    }
+}
+
+impl<O: hal::OutputPin, const D: usize, const N: usize> hal::SevenSegmentDisplay for FilledSevenSegment<O, D, N> {
+    #[inline]
+    fn digits(&self) -> usize {
+        D
+    }
+
+    #[inline]
+    fn hide_digit(&mut self, digit_index: usize) {
+        self.frame.hide_digit(digit_index);
+    }
+
+    #[inline]
+    fn show_digit(&mut self, digit_index: usize) {
+        self.frame.show_digit(digit_index);
+    }
+
+    #[inline]
+    fn hide_all_digits(&mut self) {
+        self.frame.hide_all_digits();
+    }
+
+    #[inline]
+    fn show_all_digits(&mut self) {
+        self.frame.show_all_digits();
+    }
+
+    #[inline]
+    fn set_glyph(&mut self, digit_index: usize, glyph: Option<Glyph>) {
+        self.frame.set_glyph(digit_index, glyph);
+    }
+
+    #[inline]
+    fn set_dots(&mut self, dots: u8) {
+        self.frame.set_dots(dots);
+    }
+
+    #[inline]
+    fn set_brightness(&mut self, level: u8) {
+        self.frame.set_brightness(level);
+        self.blanking.set_level(self.frame.brightness());
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.frame.clear();
+    }
+}
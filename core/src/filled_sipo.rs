@@ -0,0 +1,64 @@
+use super::hal;
+use super::sipo;
+
+// N chained shift registers, data[0] is shifted in first,
+// so it ends up in the last register of the chain.
+pub struct FilledSipo<O, const N: usize> {
+    shift_register: sipo::Sipo<O>,
+    data: [u8; N],
+    update_step: usize
+}
+
+impl<O: hal::OutputPin, const N: usize> FilledSipo<O, N> {
+    pub fn create(shift_register: sipo::Sipo<O>) -> FilledSipo<O, N> {
+        FilledSipo {
+            shift_register,
+            data: [0; N],
+            update_step: 0
+        }
+    }
+
+    pub fn set_data(&mut self, data: [u8; N]) {
+        self.data = data;
+        self.reset();
+    }
+
+    #[inline]
+    pub fn push_block(&mut self) {
+        while !self.step() {
+        }
+    }
+
+    pub fn step(&mut self) -> bool {
+        let index = self.update_step / 8;
+        let move_factor = self.update_step % 8;
+        self.shift_register.shift_value((self.data[index] >> (7 - move_factor)) & 1 == 1);
+
+        if self.update_step >= 8 * N - 1 {
+            self.update_step = 0;
+            self.shift_register.show();
+            return true;
+        }
+
+        self.update_step += 1;
+        return false;
+    }
+
+    #[inline]
+    pub fn reset(&mut self) {
+        self.update_step = 0;
+    }
+
+    pub fn clear(&mut self) {
+        self.set_data([0; N]);
+        self.reset();
+    }
+
+    // Turns the outputs off at once, without shifting the zeros in.
+    // The data is kept, the next push shows it again.
+    pub fn blank(&mut self) {
+        self.shift_register.clear();
+        self.shift_register.show();
+        self.reset();
+    }
+}
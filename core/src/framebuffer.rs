@@ -0,0 +1,224 @@
+// What the displays should show, without the pins.
+// The game draws into the frames, the drivers take a copy
+// and scan it out, on the board from the timer interrupt.
+
+use super::brightness;
+use super::glyph::Glyph;
+use super::hal;
+
+// D digits, digit 0 is the rightmost one. The hidden digits
+// and the dots are bits of a byte, so there are at most 8 digits.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct SevenSegmentFrame<const D: usize> {
+    glyphs: [Option<Glyph>; D],
+    hide: u8,
+    dots: u8,
+    brightness: u8,
+}
+
+impl<const D: usize> Default for SevenSegmentFrame<D> {
+    fn default() -> SevenSegmentFrame<D> {
+        SevenSegmentFrame {
+            glyphs: [None; D],
+            hide: 0,
+            dots: 0,
+            brightness: brightness::MAX_LEVEL,
+        }
+    }
+}
+
+impl<const D: usize> SevenSegmentFrame<D> {
+    pub fn create() -> SevenSegmentFrame<D> {
+        assert!(D <= 8);
+        SevenSegmentFrame::default()
+    }
+
+    #[inline]
+    pub fn glyph(&self, digit_index: usize) -> Option<Glyph> {
+        self.glyphs[digit_index]
+    }
+
+    #[inline]
+    pub fn hidden(&self, digit_index: usize) -> bool {
+        (self.hide & (1 << digit_index)) != 0
+    }
+
+    #[inline]
+    pub fn dot(&self, digit_index: usize) -> bool {
+        (self.dots & (1 << digit_index)) != 0
+    }
+
+    #[inline]
+    pub fn brightness(&self) -> u8 {
+        self.brightness
+    }
+}
+
+impl<const D: usize> hal::SevenSegmentDisplay for SevenSegmentFrame<D> {
+    #[inline]
+    fn digits(&self) -> usize {
+        D
+    }
+
+    #[inline]
+    fn hide_digit(&mut self, digit_index: usize) {
+        self.hide |= 1 << digit_index;
+    }
+
+    #[inline]
+    fn show_digit(&mut self, digit_index: usize) {
+        self.hide &= !(1 << digit_index);
+    }
+
+    #[inline]
+    fn hide_all_digits(&mut self) {
+        self.hide = 0xFF;
+    }
+
+    #[inline]
+    fn show_all_digits(&mut self) {
+        self.hide = 0;
+    }
+
+    fn set_glyph(&mut self, digit_index: usize, glyph: Option<Glyph>) {
+        if digit_index < D {
+            self.glyphs[digit_index] = glyph;
+        }
+    }
+
+    #[inline]
+    fn set_dots(&mut self, dots: u8) {
+        self.dots = dots;
+    }
+
+    #[inline]
+    fn set_brightness(&mut self, level: u8) {
+        self.brightness = level.min(brightness::MAX_LEVEL);
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.glyphs = [None; D];
+        self.dots = 0;
+    }
+}
+
+// Blinking of an LED, independent of the scan,
+// the drivers pass the milliseconds.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Blink {
+    Steady,
+    Slow, // once a second
+    Fast, // four times a second
+}
+
+impl Blink {
+    // Whether a blinking LED is on at the time.
+    pub fn visible(self, millis: u32) -> bool {
+        match self {
+            Blink::Steady => true,
+            Blink::Slow => millis % 1000 < 500,
+            Blink::Fast => millis % 250 < 125,
+        }
+    }
+}
+
+// How a lit LED is shown, the intensity is a brightness level
+// relative to the brightness of the whole display.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Led {
+    pub intensity: u8,
+    pub blink: Blink,
+}
+
+impl Led {
+    pub const FULL: Led = Led {
+        intensity: brightness::MAX_LEVEL,
+        blink: Blink::Steady,
+    };
+
+    pub fn create(intensity: u8, blink: Blink) -> Led {
+        Led {
+            intensity: intensity.min(brightness::MAX_LEVEL),
+            blink,
+        }
+    }
+}
+
+// W columns of H rows, a row keeps the lit LEDs as the bits of the columns,
+// so there are at most 8 columns. The attributes of the lit LEDs are
+// kept alongside.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct LEDMatrixFrame<const W: usize, const H: usize> {
+    rows: [u8; H],
+    leds: [[Led; W]; H],
+    brightness: u8,
+}
+
+impl<const W: usize, const H: usize> LEDMatrixFrame<W, H> {
+    pub fn create() -> LEDMatrixFrame<W, H> {
+        LEDMatrixFrame {
+            rows: [0; H],
+            leds: [[Led::FULL; W]; H],
+            brightness: brightness::MAX_LEVEL,
+        }
+    }
+
+    // The lit LEDs of the row, bit x for the column x.
+    #[inline]
+    pub fn row(&self, y: usize) -> u8 {
+        self.rows[y]
+    }
+
+    // The attributes of a lit LED, None when it is off.
+    pub fn led(&self, x: u8, y: u8) -> Option<Led> {
+        if !hal::LEDMatrixDisplay::get(self, x, y) {
+            return None;
+        }
+
+        Some(self.leds[usize::from(y)][usize::from(x)])
+    }
+
+    #[inline]
+    pub fn brightness(&self) -> u8 {
+        self.brightness
+    }
+}
+
+impl<const W: usize, const H: usize> hal::LEDMatrixDisplay for LEDMatrixFrame<W, H> {
+    #[inline]
+    fn width(&self) -> u8 {
+        W as u8
+    }
+
+    #[inline]
+    fn height(&self) -> u8 {
+        H as u8
+    }
+
+    // LEDs outside of the matrix are off.
+    fn get(&self, x: u8, y: u8) -> bool {
+        let (x, y) = (usize::from(x), usize::from(y));
+        x < W && y < H && self.rows[y] & (1 << x) != 0
+    }
+
+    // LEDs outside of the matrix are ignored.
+    fn set_led(&mut self, x: u8, y: u8, led: Led) {
+        let (x, y) = (usize::from(x), usize::from(y));
+        if x < W && y < H {
+            self.rows[y] |= 1 << x;
+            self.leds[y][x] = led;
+        }
+    }
+
+    #[inline]
+    fn set_brightness(&mut self, level: u8) {
+        self.brightness = level.min(brightness::MAX_LEVEL);
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.rows = [0; H];
+        self.leds = [[Led::FULL; W]; H];
+    }
+}
@@ -0,0 +1,942 @@
+use super::animation;
+use super::brightness;
+use super::button;
+use super::clock;
+use super::entropy;
+use super::framebuffer;
+use super::hal;
+use super::history;
+use super::mode;
+use super::rng;
+use super::rules;
+use super::scores;
+use super::settings;
+use super::stats;
+use super::storage;
+use super::scoring;
+
+// Digits of the original board, the game takes 2 to 8 digits,
+// the labels and the time need at least 2.
+pub const DIGITS: usize = 4;
+// How long the time left is shown, in milliseconds.
+const COUNTDOWN_FLASH_MILLIS: u32 = 1000;
+const LED_MATRIX_CORRECT_ROW: u8 = 0;
+const LED_MATRIX_INCORRECT_POSITION_ROW: u8 = 1;
+// The yellow LEDs blink, so they are told apart from the red ones in a poor light.
+const LED_MATRIX_INCORRECT_POSITION_LED: framebuffer::Led = framebuffer::Led {
+    intensity: brightness::MAX_LEVEL,
+    blink: framebuffer::Blink::Slow,
+};
+
+pub struct Game<S, M, I, R, C, const D: usize> {
+    seven_segment: S,
+    led_matrix: M,
+    state: GameState,
+    guessing_number: Option<[u8; D]>,
+    current_number: Option<[u8; D]>,
+    animations: animation::Animations<D>,
+    animation: Option<animation::AnimationKind>,
+    rng: R,
+    clock: C,
+    stopwatch: clock::Stopwatch,
+    rules: rules::GameRules, // of the running game
+    next_rules: rules::GameRules, // taken when the next game starts
+    idle_steps: u32,
+    attempts: u16,
+    high_scores: scores::HighScores,
+    statistics: stats::Statistics,
+    stats_page: usize,
+    settings: settings::Settings,
+    settings_page: usize,
+    settings_changed: bool, // saved when the menu is left
+    save_requested: bool,
+    confirm_armed: bool, // confirm was pressed on this screen, not on the one before
+    confirm_long: bool, // the armed confirm is held for a long click
+    confirm_pending: bool, // released, a guess unless it turns into a double click
+    cursor: usize, // digit index changed in the cursor input mode
+    history: history::History<D>,
+    history_age: usize, // the browsed guess, 0 is the newest
+    mode_index: usize, // the mode of the next game, see mode::GameMode::from_index
+    game_mode: mode::GameMode, // of the running game
+    countdown_flash: Option<u32>, // since when the time left is shown instead of the entry
+    buttons: [button::Button<I>; D],
+    confirm: button::Button<I>,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum DigitPress {
+    None,
+    Press, // pressed, or repeated while held
+    DoubleClick,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum GameState {
+    Start,
+    Play,
+    Won,
+    Lost, // out of attempts or time, the secret is revealed
+    ModeSelect, // picking the mode of the next game
+    Stats, // pages of the high scores and statistics
+    Settings, // the settings menu, brightness of the displays
+    History, // browsing the guesses of the running game
+}
+
+impl<S, M, I, R, C, const D: usize> Game<S, M, I, R, C, D>
+where
+    S: hal::SevenSegmentDisplay,
+    M: hal::LEDMatrixDisplay,
+    I: hal::InputPin,
+    R: rng::Rng,
+    C: clock::Clock,
+{
+    // Evaluated by create, so other digit counts do not compile.
+    const DIGITS_SUPPORTED: () = assert!(D >= 2 && D <= 8, "the game takes 2 to 8 digits");
+
+    pub fn create(
+        seven_segment: S,
+        led_matrix: M,
+        buttons: [button::Button<I>; D],
+        confirm: button::Button<I>,
+        rng: R,
+        clock: C
+    ) -> Game<S, M, I, R, C, D> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::DIGITS_SUPPORTED;
+
+        Game {
+            seven_segment,
+            led_matrix,
+            state: GameState::Start,
+            guessing_number: None,
+            current_number: None,
+            animations: animation::Animations::create(),
+            animation: None,
+            rng,
+            clock,
+            stopwatch: clock::Stopwatch::create(),
+            rules: rules::GameRules {
+                length: D as u8,
+                ..rules::GameRules::default()
+            },
+            next_rules: rules::GameRules {
+                length: D as u8,
+                ..rules::GameRules::default()
+            },
+            idle_steps: 0,
+            attempts: 0,
+            high_scores: scores::HighScores::default(),
+            statistics: stats::Statistics::default(),
+            stats_page: 0,
+            settings: settings::Settings {
+                rules: rules::GameRules {
+                    length: D as u8,
+                    ..rules::GameRules::default()
+                },
+                ..settings::Settings::default()
+            },
+            settings_page: 0,
+            settings_changed: false,
+            save_requested: false,
+            confirm_armed: false,
+            confirm_long: false,
+            confirm_pending: false,
+            cursor: 0,
+            history: history::History::create(),
+            history_age: 0,
+            mode_index: 0,
+            game_mode: mode::GameMode::Classic,
+            countdown_flash: None,
+            buttons,
+            confirm,
+        }
+    }
+
+    #[inline]
+    pub fn seven_segment(&mut self) -> &mut S {
+        &mut self.seven_segment
+    }
+
+    #[inline]
+    pub fn led_matrix(&mut self) -> &mut M {
+        &mut self.led_matrix
+    }
+
+    // Both displays at once, to hand them over for showing.
+    #[inline]
+    pub fn displays(&self) -> (&S, &M) {
+        (&self.seven_segment, &self.led_matrix)
+    }
+
+    // The rules of the running game, or of the last one.
+    #[inline]
+    pub fn rules(&self) -> &rules::GameRules {
+        &self.rules
+    }
+
+    #[inline]
+    pub fn state(&self) -> GameState {
+        self.state
+    }
+
+    // Confirmed guesses of the current game, the winning one included.
+    // Kept after the win until the next game starts.
+    #[inline]
+    pub fn attempts(&self) -> u16 {
+        self.attempts
+    }
+
+    // Milliseconds of the current game, after the win the time it took.
+    #[inline]
+    pub fn elapsed(&self) -> u32 {
+        self.stopwatch.elapsed(self.clock.millis())
+    }
+
+    // The secret of the running game, for the tests and the simulator.
+    #[inline]
+    pub fn secret(&self) -> Option<[u8; D]> {
+        self.guessing_number
+    }
+
+    #[inline]
+    pub fn high_scores(&self) -> &scores::HighScores {
+        &self.high_scores
+    }
+
+    // Loaded from the storage, does not request a save.
+    #[inline]
+    pub fn set_high_scores(&mut self, high_scores: scores::HighScores) {
+        self.high_scores = high_scores;
+    }
+
+    pub fn reset_high_scores(&mut self) {
+        self.high_scores.reset();
+        self.save_requested = true;
+    }
+
+    #[inline]
+    pub fn statistics(&self) -> &stats::Statistics {
+        &self.statistics
+    }
+
+    // Loaded from the storage, does not request a save.
+    #[inline]
+    pub fn set_statistics(&mut self, statistics: stats::Statistics) {
+        self.statistics = statistics;
+    }
+
+    #[inline]
+    pub fn settings(&self) -> &settings::Settings {
+        &self.settings
+    }
+
+    // Loaded from the storage, applied to the displays at once,
+    // does not request a save.
+    pub fn set_settings(&mut self, settings: settings::Settings) {
+        self.settings = settings;
+        self.apply_settings();
+    }
+
+    // Takes the persistent data of the game from the record.
+    pub fn load(&mut self, record: &storage::Record) {
+        self.set_high_scores(record.high_scores);
+        self.set_statistics(record.statistics);
+        self.set_settings(record.settings);
+    }
+
+    // Puts the persistent data of the game into the record.
+    pub fn save(&self, record: &mut storage::Record) {
+        record.high_scores = self.high_scores;
+        record.statistics = self.statistics;
+        record.settings = self.settings;
+    }
+
+    // Whether the persistent data changed since the last call,
+    // the caller writes it to the storage.
+    pub fn take_save_request(&mut self) -> bool {
+        let save_requested = self.save_requested;
+        self.save_requested = false;
+
+        save_requested
+    }
+
+    // Called once before the game loop, debounces the buttons
+    // and tells whether the first and the last digit buttons are held.
+    pub fn reset_combination_held(&mut self) -> bool {
+        for _ in 0..button::DEBOUNCECYCLES {
+            self.step_buttons();
+        }
+
+        let held = |button: &button::Button<I>| {
+            let state = button.state();
+            state == button::ButtonState::Active || state == button::ButtonState::Pressed
+        };
+
+        held(&self.buttons[0]) && held(&self.buttons[D - 1])
+    }
+
+    // The rules are used from the next game on,
+    // the running game keeps the ones its secret was made by.
+    pub fn set_rules(&mut self, rules: rules::GameRules) -> bool {
+        if !rules.valid(D) {
+            return false;
+        }
+
+        self.next_rules = rules;
+        return true;
+    }
+
+    pub fn step_buttons(&mut self) {
+        for button in self.buttons.iter_mut() {
+            button.step();
+        }
+        self.confirm.step();
+    }
+
+    pub fn step_animation(&mut self) {
+        if let Some(kind) = self.animation {
+            let animation = self.animations.get(kind);
+            if animation.running() {
+                let state = animation.step(&mut self.seven_segment, &mut self.led_matrix);
+
+                if state == animation::AnimationState::End {
+                    animation.cleanup(&mut self.seven_segment, &mut self.led_matrix);
+                    self.animation = None;
+                }
+            } else {
+                animation.cleanup(&mut self.seven_segment, &mut self.led_matrix);
+                self.animation = None;
+            }
+        }
+    }
+
+    pub fn step(&mut self) {
+        match self.state {
+            GameState::Start | GameState::Won | GameState::Lost => {
+                // how long it takes the player to press a button is random
+                self.idle_steps = self.idle_steps.wrapping_add(1);
+
+                // a long press of confirm shows the statistics,
+                // a short one starts the game when released
+                let confirm_event = self.confirm.event();
+                match self.confirm.state() {
+                    button::ButtonState::Pressed => self.confirm_armed = true,
+                    button::ButtonState::Released if self.confirm_armed => {
+                        self.confirm_armed = false;
+                        self.start_new_game();
+                        return;
+                    }
+                    _ => {}
+                }
+
+                if self.confirm_armed && confirm_event == button::ButtonEvent::LongClick {
+                    self.confirm_armed = false;
+                    self.open_stats();
+                    return;
+                }
+
+                if self.any_digit_button_pressed() {
+                    self.open_mode_select();
+                }
+            }
+            GameState::ModeSelect => {
+                self.idle_steps = self.idle_steps.wrapping_add(1);
+
+                // confirm starts the game in the shown mode when released
+                match self.confirm.state() {
+                    button::ButtonState::Pressed => self.confirm_armed = true,
+                    button::ButtonState::Released if self.confirm_armed => {
+                        self.confirm_armed = false;
+                        self.start_new_game();
+                        return;
+                    }
+                    _ => {}
+                }
+
+                // the first digit button goes back, the others forward
+                for (i, button) in self.buttons.iter().enumerate() {
+                    if button.state() == button::ButtonState::Pressed {
+                        self.mode_index = if i == 0 {
+                            (self.mode_index + mode::MODES - 1) % mode::MODES
+                        } else {
+                            (self.mode_index + 1) % mode::MODES
+                        };
+                        self.show_mode();
+                        break;
+                    }
+                }
+            }
+            GameState::Stats => {
+                self.idle_steps = self.idle_steps.wrapping_add(1);
+
+                // a long press of confirm opens the settings,
+                // a short one goes back to the start screen when released
+                let confirm_event = self.confirm.event();
+                match self.confirm.state() {
+                    button::ButtonState::Pressed => self.confirm_armed = true,
+                    button::ButtonState::Released if self.confirm_armed => {
+                        self.confirm_armed = false;
+                        self.close_menu();
+                        return;
+                    }
+                    _ => {}
+                }
+
+                if self.confirm_armed && confirm_event == button::ButtonEvent::LongClick {
+                    self.confirm_armed = false;
+                    self.open_settings();
+                    return;
+                }
+
+                // the first digit button goes back, the others forward
+                for (i, button) in self.buttons.iter().enumerate() {
+                    if button.state() == button::ButtonState::Pressed {
+                        self.stats_page = if i == 0 {
+                            (self.stats_page + stats::PAGES - 1) % stats::PAGES
+                        } else {
+                            (self.stats_page + 1) % stats::PAGES
+                        };
+                        self.show_stats_page();
+                        break;
+                    }
+                }
+            }
+            GameState::Settings => {
+                self.idle_steps = self.idle_steps.wrapping_add(1);
+
+                if self.confirm.state() == button::ButtonState::Pressed {
+                    self.close_menu();
+                    return;
+                }
+
+                // the left half of the digit buttons picks the setting,
+                // back and forward, the right half changes it, down and up
+                for (i, button) in self.buttons.iter().enumerate() {
+                    if button.state() != button::ButtonState::Pressed {
+                        continue;
+                    }
+
+                    if i < D / 2 {
+                        self.settings_page = if i == 0 {
+                            (self.settings_page + settings::PAGES - 1) % settings::PAGES
+                        } else {
+                            (self.settings_page + 1) % settings::PAGES
+                        };
+                    } else {
+                        // rules the digits can not play are not taken
+                        let previous = self.settings;
+                        if self.settings.change(self.settings_page, i == D - 1) {
+                            if self.settings.rules.valid(D) {
+                                self.settings_changed = true;
+                                self.apply_settings();
+                            } else {
+                                self.settings = previous;
+                            }
+                        }
+                    }
+
+                    self.show_settings_page();
+                    break;
+                }
+            }
+            GameState::Play => {
+                if self.time_is_up() {
+                    self.lose_current_game();
+                    return;
+                }
+
+                // any button brings the entry back
+                if let Some(shown) = self.countdown_flash {
+                    let over = self.clock.millis().wrapping_sub(shown) >= COUNTDOWN_FLASH_MILLIS;
+                    if over || self.any_button_pressed() {
+                        self.countdown_flash = None;
+                        self.show_entry();
+                    }
+                }
+
+                // confirm takes the guess once it is clear it was a click,
+                // a double click browses the guesses and after a long press
+                // it gives up. Used as the shift it does none of them.
+                let confirm_event = self.confirm.event();
+                match self.confirm.state() {
+                    button::ButtonState::Pressed => {
+                        self.confirm_armed = true;
+                        self.confirm_long = false;
+                    }
+                    button::ButtonState::Released if self.confirm_armed => {
+                        self.confirm_armed = false;
+                        if self.confirm_long {
+                            self.give_up();
+                            return;
+                        }
+                        self.confirm_pending = true;
+                    }
+                    _ => {}
+                }
+
+                if self.confirm_pending {
+                    match confirm_event {
+                        button::ButtonEvent::Click => {
+                            self.confirm_pending = false;
+                            self.confirm_guess();
+                            return;
+                        }
+                        button::ButtonEvent::DoubleClick => {
+                            self.confirm_pending = false;
+                            self.confirm_armed = false;
+                            self.open_history();
+                            return;
+                        }
+                        _ => {}
+                    }
+                }
+
+                if self.confirm_armed && confirm_event == button::ButtonEvent::LongClick {
+                    self.confirm_long = true;
+                }
+
+                // a press, repeated while held
+                let mut presses = [DigitPress::None; D];
+                for (i, button) in self.buttons.iter_mut().enumerate() {
+                    presses[i] = match button.event() {
+                        button::ButtonEvent::DoubleClick => DigitPress::DoubleClick,
+                        button::ButtonEvent::LongClick | button::ButtonEvent::LongClickContinuous => DigitPress::Press,
+                        _ if button.state() == button::ButtonState::Pressed => DigitPress::Press,
+                        _ => DigitPress::None,
+                    };
+                }
+
+                // a digit pressed before the click shows takes the guess first
+                if self.confirm_pending && presses.iter().any(|press| *press != DigitPress::None) {
+                    self.confirm_pending = false;
+                    self.confirm_guess();
+                    if self.state != GameState::Play {
+                        return;
+                    }
+                }
+
+                for (i, press) in presses.iter().enumerate() {
+                    if *press != DigitPress::None {
+                        self.digit_pressed(i, *press);
+                    }
+                }
+            }
+            GameState::History => {
+                if self.time_is_up() {
+                    self.lose_current_game();
+                    return;
+                }
+
+                if self.confirm.state() == button::ButtonState::Pressed {
+                    self.close_history();
+                    return;
+                }
+
+                // the first digit button goes to older guesses, the others
+                // to newer ones and past the newest back to the entry
+                for (i, button) in self.buttons.iter().enumerate() {
+                    if button.state() != button::ButtonState::Pressed {
+                        continue;
+                    }
+
+                    if i == 0 {
+                        if self.history_age + 1 < self.history.len() {
+                            self.history_age += 1;
+                        }
+                    } else if self.history_age == 0 {
+                        self.close_history();
+                        return;
+                    } else {
+                        self.history_age -= 1;
+                    }
+
+                    self.show_history_guess();
+                    break;
+                }
+            }
+        }
+    }
+
+    pub fn set_animation(&mut self, animation: animation::AnimationKind) {
+        if let Some(current_animation) = self.animation {
+            self.animations.get(current_animation).cleanup(&mut self.seven_segment, &mut self.led_matrix);
+        }
+
+        self.animation = Some(animation);
+    }
+
+    fn stop_animation(&mut self) {
+        if let Some(animation) = self.animation {
+            self.animations.get(animation).cleanup(&mut self.seven_segment, &mut self.led_matrix);
+            self.animation = None;
+        }
+    }
+
+    // Scrolls the text across the display,
+    // animation::MARQUEE_STEPS_PER_SHIFT steps are a quarter of a second.
+    pub fn show_text(&mut self, text: &'static dyn hal::Text, steps_per_shift: u16) {
+        self.set_animation(animation::AnimationKind::Marquee);
+        self.animations.marquee.reset(text, steps_per_shift);
+    }
+
+    fn update_led_matrix(&mut self) -> [scoring::Mark; D] {
+        let current_digits = self.current_number.unwrap();
+        let guessing_digits = self.guessing_number.unwrap();
+
+        let length = self.rules.length();
+        let mut marks = [scoring::Mark::Absent; D];
+        scoring::score(&guessing_digits[..length], &current_digits[..length], &mut marks[..length]);
+
+        self.show_marks(&marks);
+        marks
+    }
+
+    fn show_marks(&mut self, marks: &[scoring::Mark; D]) {
+        self.led_matrix.clear();
+
+        for (i, mark) in marks[..self.rules.length()].iter().enumerate() {
+            match mark {
+                scoring::Mark::Correct => {
+                    self.led_matrix.set(i.try_into().unwrap(), LED_MATRIX_CORRECT_ROW);
+                }
+                scoring::Mark::Misplaced => {
+                    self.led_matrix.set_led(
+                        i.try_into().unwrap(),
+                        LED_MATRIX_INCORRECT_POSITION_ROW,
+                        LED_MATRIX_INCORRECT_POSITION_LED
+                    );
+                }
+                scoring::Mark::Absent => {}
+            }
+        }
+    }
+
+    // What the digit button i does depends on the input mode.
+    fn digit_pressed(&mut self, i: usize, press: DigitPress) {
+        let shift = matches!(self.confirm.state(), button::ButtonState::Pressed | button::ButtonState::Active);
+        let length = self.rules.length();
+
+        let (digit_index, change) = match self.settings.input_mode {
+            settings::InputMode::Shift if shift => {
+                // confirm was the shift, it does not take the guess
+                self.confirm_armed = false;
+                (D - 1 - i, -1)
+            }
+            settings::InputMode::Count | settings::InputMode::Shift => {
+                // the first click of a double click already counted up,
+                // so the second one takes two off to go one down
+                let change = if press == DigitPress::DoubleClick { -2 } else { 1 };
+                (D - 1 - i, change)
+            }
+            settings::InputMode::Cursor if i < D / 2 => {
+                self.cursor = if i == 0 {
+                    (self.cursor + 1) % length
+                } else {
+                    (self.cursor + length - 1) % length
+                };
+                self.show_entry();
+                return;
+            }
+            settings::InputMode::Cursor => {
+                let change = if i == D - 1 { 1 } else { -1 };
+                (self.cursor, change)
+            }
+        };
+
+        if digit_index < length {
+            self.change_digit(digit_index, change);
+            self.set_animation(animation::AnimationKind::DigitIncrement);
+            self.animations.digit_increment.reset(digit_index);
+        }
+    }
+
+    fn confirm_guess(&mut self) {
+        self.attempts = self.attempts.saturating_add(1);
+
+        if self.current_number == self.guessing_number {
+            self.end_current_game();
+            return;
+        }
+
+        let marks = self.update_led_matrix();
+        self.history.push(history::Guess {
+            number: self.current_number.unwrap(),
+            marks,
+        });
+
+        let max_attempts = u16::from(self.settings.max_attempts);
+        if max_attempts != 0 && self.attempts >= max_attempts {
+            self.lose_current_game();
+            return;
+        }
+
+        // against the clock the time left is shown instead of the blink
+        if self.time_left().is_some() {
+            self.flash_time_left();
+            return;
+        }
+
+        self.set_animation(animation::AnimationKind::Guess);
+        self.animations.guess.reset();
+    }
+
+    // None without a time limit.
+    fn time_left(&self) -> Option<u32> {
+        let time_limit = self.game_mode.time_limit()?;
+        Some(time_limit.saturating_sub(self.stopwatch.elapsed(self.clock.millis())))
+    }
+
+    #[inline]
+    fn time_is_up(&self) -> bool {
+        self.time_left() == Some(0)
+    }
+
+    // Shows the time left as MM.SS for a second,
+    // it is not updated while shown.
+    fn flash_time_left(&mut self) {
+        let time_left = self.time_left();
+        self.seven_segment.set_glyphs(&animation::time_digits::<D>(time_left));
+        self.seven_segment.set_dots(animation::TIME_DOTS);
+        self.countdown_flash = Some(self.clock.millis());
+    }
+
+    // Wraps around within the alphabet of the rules.
+    fn change_digit(&mut self, digit_index: usize, change: i8) {
+        let mut current_number = self.current_number.unwrap();
+
+        let alphabet = self.rules.alphabet as i8;
+        let current_digit = (current_number[digit_index] as i8 + change).rem_euclid(alphabet) as u8;
+
+        current_number[digit_index] = current_digit;
+        self.current_number = Some(current_number);
+        self.show_entry();
+    }
+
+    // The number being entered, the cursor input mode
+    // shows the cursor as the dot of its digit.
+    fn show_entry(&mut self) {
+        self.show_number(self.current_number.unwrap());
+        if self.settings.input_mode == settings::InputMode::Cursor {
+            self.seven_segment.set_dots(1 << self.cursor);
+        }
+    }
+
+    // Shows the digits of the code, the positions outside of it stay blank.
+    fn show_number(&mut self, number: [u8; D]) {
+        self.seven_segment.set_number(&number);
+        self.seven_segment.set_dots(0);
+        for i in self.rules.length()..D {
+            self.seven_segment.set_digit(i, None);
+        }
+    }
+
+    // Nothing to browse before the first guess.
+    fn open_history(&mut self) {
+        if self.history.is_empty() {
+            return;
+        }
+
+        self.stop_animation();
+
+        self.countdown_flash = None;
+        self.history_age = 0;
+        self.show_history_guess();
+        self.state = GameState::History;
+    }
+
+    // A past guess has all the dots of the code lit,
+    // so it is not taken for the entry.
+    fn show_history_guess(&mut self) {
+        let guess = *self.history.get(self.history_age).unwrap();
+        self.show_number(guess.number);
+        self.seven_segment.set_dots(u8::MAX >> (8 - self.rules.length()));
+        self.show_marks(&guess.marks);
+    }
+
+    // Back to the entry with the marks of the newest guess.
+    fn close_history(&mut self) {
+        let newest = *self.history.get(0).unwrap();
+        self.show_entry();
+        self.show_marks(&newest.marks);
+        self.confirm_armed = false;
+        self.drop_events();
+        self.state = GameState::Play;
+    }
+
+    fn open_mode_select(&mut self) {
+        self.stop_animation();
+
+        self.led_matrix.clear();
+        self.confirm_armed = false;
+        self.show_mode();
+        self.state = GameState::ModeSelect;
+    }
+
+    fn show_mode(&mut self) {
+        let digits = mode::page_digits::<D>(mode::GameMode::from_index(self.mode_index));
+        self.seven_segment.set_glyphs(&digits);
+        self.seven_segment.set_dots(0);
+    }
+
+    fn open_stats(&mut self) {
+        self.stop_animation();
+
+        self.led_matrix.clear();
+        self.stats_page = 0;
+        self.show_stats_page();
+        self.state = GameState::Stats;
+    }
+
+    fn show_stats_page(&mut self) {
+        let (digits, dots) = stats::page_digits::<D>(self.stats_page, &self.high_scores, &self.statistics);
+        self.seven_segment.set_glyphs(&digits);
+        self.seven_segment.set_dots(dots);
+    }
+
+    fn open_settings(&mut self) {
+        self.settings_page = 0;
+        self.settings_changed = false;
+        self.show_settings_page();
+        self.state = GameState::Settings;
+    }
+
+    fn show_settings_page(&mut self) {
+        let digits = settings::page_digits::<D>(self.settings_page, &self.settings);
+        self.seven_segment.set_glyphs(&digits);
+        self.seven_segment.set_dots(0);
+    }
+
+    // The rules wait for the next game, the ones
+    // the digits can not play, from the storage, are left out.
+    fn apply_settings(&mut self) {
+        self.seven_segment.set_brightness(self.settings.seven_segment_brightness);
+        self.led_matrix.set_brightness(self.settings.led_matrix_brightness);
+        self.set_rules(self.settings.rules);
+    }
+
+    // Back to the start screen from the statistics or the settings,
+    // changed settings are saved once here, not on every press.
+    fn close_menu(&mut self) {
+        if self.settings_changed {
+            self.settings_changed = false;
+            self.save_requested = true;
+        }
+
+        self.animations.hello.reset();
+        self.set_animation(animation::AnimationKind::Hello);
+        self.state = GameState::Start;
+    }
+
+    fn end_current_game(&mut self) {
+        let time = self.stopwatch.stop(self.clock.millis());
+
+        self.high_scores.record_attempts(self.attempts);
+        self.high_scores.record_time(time);
+        self.statistics.record_win(self.attempts);
+        self.save_requested = true;
+
+        self.set_animation(animation::AnimationKind::Win);
+        self.animations.win.reset(self.guessing_number.unwrap(), self.rules.length(), self.attempts, time);
+        self.cleanup_current_game();
+        self.state = GameState::Won;
+    }
+
+    // Out of attempts or time, reveals the secret.
+    fn lose_current_game(&mut self) {
+        self.stopwatch.stop(self.clock.millis());
+
+        self.statistics.record_loss();
+        self.save_requested = true;
+
+        self.set_animation(animation::AnimationKind::Lose);
+        self.animations.lose.reset(self.guessing_number.unwrap(), self.rules.length());
+        self.cleanup_current_game();
+        self.state = GameState::Lost;
+    }
+
+    // Shows the secret and goes back to the start screen,
+    // the game counts as abandoned.
+    fn give_up(&mut self) {
+        self.stopwatch.stop(self.clock.millis());
+
+        self.stop_animation();
+
+        self.led_matrix.clear();
+        self.show_number(self.guessing_number.unwrap());
+        self.cleanup_current_game();
+        self.state = GameState::Start;
+    }
+
+    // A confirm held into the end of the game does not start
+    // the next one on the release.
+    fn cleanup_current_game(&mut self) {
+        self.guessing_number = None;
+        self.current_number = None;
+        self.confirm_armed = false;
+        self.confirm_long = false;
+        self.confirm_pending = false;
+        self.drop_events();
+    }
+
+    #[inline]
+    fn generate_number(&mut self) -> [u8; D] {
+        self.rules.generate_number(&mut self.rng)
+    }
+
+    fn start_new_game(&mut self) {
+        self.stop_animation();
+
+        self.rng.mix(entropy::spread(self.idle_steps).to_le_bytes());
+        self.idle_steps = 0;
+        self.attempts = 0;
+        self.confirm_armed = false;
+        self.confirm_long = false;
+        self.confirm_pending = false;
+        self.rules = self.next_rules;
+        self.cursor = self.rules.length() - 1;
+        self.history.clear();
+        self.game_mode = mode::GameMode::from_index(self.mode_index);
+        self.countdown_flash = None;
+        self.drop_events();
+        self.statistics.record_start();
+        self.save_requested = true;
+        self.stopwatch.start(self.clock.millis());
+
+        let guessing_number = self.generate_number();
+        let current_number = [0; D];
+
+        self.guessing_number = Some(guessing_number);
+        self.current_number = Some(current_number);
+        self.show_entry();
+        self.led_matrix.clear();
+        if self.time_left().is_some() {
+            self.flash_time_left();
+        }
+
+        self.state = GameState::Play;
+    }
+
+    // The events of the screen before.
+    fn drop_events(&mut self) {
+        for button in self.buttons.iter_mut() {
+            button.event();
+        }
+        self.confirm.event();
+    }
+
+    fn any_button_pressed(&self) -> bool {
+        self.any_digit_button_pressed() || self.confirm.state() == button::ButtonState::Pressed
+    }
+
+    fn any_digit_button_pressed(&self) -> bool {
+        for btn in self.buttons.iter() {
+            let state = btn.state();
+
+            if state == button::ButtonState::Pressed {
+                return true;
+            }
+        }
+
+        false
+    }
+}
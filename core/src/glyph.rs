@@ -0,0 +1,190 @@
+// What a digit of the seven segment display shows, kept as its segments.
+// Numbers come from Glyph::digit and characters from Glyph::ascii,
+// so Glyph::digit(1) and Glyph::ascii(b'1') are the same glyph.
+
+use super::hal::Text;
+use super::progmem::ProgmemText;
+
+// The segments of the printable ASCII characters from the space on.
+// Characters that can not be shown are 0, as the space itself,
+// K, M, V, W and X need diagonals and are not in the font.
+// Read by the constants only, the game reads FONT_PROGMEM.
+const FONT_START: u8 = b' ';
+const FONT: [u8; 95] = [
+    //HGFEDCBA
+    0b00000000, // space
+    0b00000000, // !
+    0b00100010, // "
+    0b00000000, // #
+    0b00000000, // $
+    0b00000000, // %
+    0b00000000, // &
+    0b00100000, // '
+    0b00111001, // (
+    0b00001111, // )
+    0b00000000, // *
+    0b00000000, // +
+    0b00010000, // ,
+    0b01000000, // -
+    0b10000000, // .
+    0b01010010, // /
+    0b00111111, // 0
+    0b00000110, // 1
+    0b01011011, // 2
+    0b01001111, // 3
+    0b01100110, // 4
+    0b01101101, // 5
+    0b01111101, // 6
+    0b00000111, // 7
+    0b01111111, // 8
+    0b01101111, // 9
+    0b00000000, // :
+    0b00000000, // ;
+    0b00000000, // <
+    0b01001000, // =
+    0b00000000, // >
+    0b01010011, // ?
+    0b00000000, // @
+    0b01110111, // A
+    0b01111100, // B, as b
+    0b00111001, // C
+    0b01011110, // D, as d
+    0b01111001, // E
+    0b01110001, // F
+    0b00111101, // G
+    0b01110110, // H
+    0b00000110, // I
+    0b00011110, // J
+    0b00000000, // K
+    0b00111000, // L
+    0b00000000, // M
+    0b00110111, // N
+    0b00111111, // O
+    0b01110011, // P
+    0b01100111, // Q
+    0b01010000, // R, as r
+    0b01101101, // S
+    0b01111000, // T, as t
+    0b00111110, // U
+    0b00000000, // V
+    0b00000000, // W
+    0b00000000, // X
+    0b01101110, // Y
+    0b01011011, // Z
+    0b00111001, // [
+    0b01100100, // backslash
+    0b00001111, // ]
+    0b00100011, // ^
+    0b00001000, // _
+    0b00000010, // `
+    0b01011111, // a
+    0b01111100, // b
+    0b01011000, // c
+    0b01011110, // d
+    0b01111011, // e
+    0b01110001, // f
+    0b01101111, // g
+    0b01110100, // h
+    0b00000100, // i
+    0b00001100, // j
+    0b00000000, // k
+    0b00110000, // l
+    0b00000000, // m
+    0b01010100, // n
+    0b01011100, // o
+    0b01110011, // p
+    0b01100111, // q
+    0b01010000, // r
+    0b01101101, // s
+    0b01111000, // t
+    0b00011100, // u
+    0b00000000, // v
+    0b00000000, // w
+    0b00000000, // x
+    0b01101110, // y
+    0b01011011, // z
+    0b00000000, // {
+    0b00110000, // |
+    0b00000000, // }
+    0b00000000, // ~
+];
+
+// The font in the flash of the board, it takes no RAM.
+#[cfg_attr(target_arch = "avr", link_section = ".progmem.data")]
+static FONT_BYTES: [u8; FONT.len()] = FONT;
+static FONT_PROGMEM: ProgmemText = ProgmemText(&FONT_BYTES);
+
+// Segments HGFEDCBA, H is the decimal point.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Glyph(u8);
+
+impl Glyph {
+    pub const BLANK: Glyph = Glyph(0);
+    pub const DASH: Glyph = Glyph(0b01000000);
+    // Shown for the characters not in the font, the top, middle and bottom bars.
+    pub const FALLBACK: Glyph = Glyph(0b01001001);
+
+    #[inline]
+    pub const fn from_segments(segments: u8) -> Glyph {
+        Glyph(segments)
+    }
+
+    #[inline]
+    pub const fn segments(self) -> u8 {
+        self.0
+    }
+
+    // Numbers from 0 to 9, the others are the fallback.
+    pub fn digit(digit: u8) -> Glyph {
+        if digit > 9 {
+            return Glyph::FALLBACK;
+        }
+        Glyph(FONT_PROGMEM.byte(usize::from(b'0' + digit - FONT_START)))
+    }
+
+    // None for the characters that can not be shown.
+    pub fn try_ascii(character: u8) -> Option<Glyph> {
+        if character < FONT_START {
+            return None;
+        }
+
+        Glyph::font_glyph(character, FONT_PROGMEM.byte(usize::from(character - FONT_START)))
+    }
+
+    pub fn ascii(character: u8) -> Glyph {
+        Glyph::try_ascii(character).unwrap_or(Glyph::FALLBACK)
+    }
+
+    // As Glyph::ascii, for the constants. It reads FONT when compiling,
+    // called while running it would copy the font to the RAM.
+    pub const fn const_ascii(character: u8) -> Glyph {
+        let index = character.wrapping_sub(FONT_START) as usize;
+        if index >= FONT.len() {
+            return Glyph::FALLBACK;
+        }
+
+        match Glyph::font_glyph(character, FONT[index]) {
+            Some(glyph) => glyph,
+            None => Glyph::FALLBACK,
+        }
+    }
+
+    // The segments 0 are no glyph, apart from the space.
+    #[inline]
+    const fn font_glyph(character: u8, segments: u8) -> Option<Glyph> {
+        if segments == 0 && character != b' ' {
+            return None;
+        }
+        Some(Glyph(segments))
+    }
+
+    // None for the characters not in the font, those out of ASCII
+    // and the ones needing diagonals, K, M, V, W and X in both cases.
+    pub fn from_char(character: char) -> Option<Glyph> {
+        if character.is_ascii() {
+            Glyph::try_ascii(character as u8)
+        } else {
+            None
+        }
+    }
+}
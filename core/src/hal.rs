@@ -0,0 +1,139 @@
+// Traits the game logic is written against.
+// The firmware implements the pin traits for the AVR pins,
+// the display drivers implement the display traits,
+// so the game itself does not depend on atmega_hal.
+
+use super::framebuffer;
+use super::glyph::Glyph;
+
+pub trait InputPin {
+    fn is_low(&self) -> bool;
+
+    #[inline]
+    fn is_high(&self) -> bool {
+        !self.is_low()
+    }
+}
+
+pub trait OutputPin {
+    fn set_high(&mut self);
+    fn set_low(&mut self);
+}
+
+pub trait SevenSegmentDisplay {
+    fn digits(&self) -> usize;
+    fn hide_digit(&mut self, digit_index: usize);
+    fn show_digit(&mut self, digit_index: usize);
+    fn hide_all_digits(&mut self);
+    fn show_all_digits(&mut self);
+    fn set_glyph(&mut self, digit_index: usize, glyph: Option<Glyph>);
+
+    // A number from 0 to 9.
+    #[inline]
+    fn set_digit(&mut self, digit_index: usize, digit: Option<u8>) {
+        self.set_glyph(digit_index, digit.map(Glyph::digit));
+    }
+
+    // Glyph i goes to the digit i of the display,
+    // the extra digits of either are left out.
+    fn set_glyphs(&mut self, glyphs: &[Option<Glyph>]) {
+        for (digit_index, glyph) in glyphs.iter().enumerate().take(self.digits()) {
+            self.set_glyph(digit_index, *glyph);
+        }
+    }
+
+    // Digit i of the number goes to the digit i of the display,
+    // the extra digits of either are left out.
+    fn set_number(&mut self, number: &[u8]) {
+        for (digit_index, digit) in number.iter().enumerate().take(self.digits()) {
+            self.set_digit(digit_index, Some(*digit));
+        }
+    }
+
+    // The text from the leftmost digit, the digits after it are blank,
+    // the characters that do not fit are left out. A '.' lights the dot
+    // of the character before it, the characters not in the font
+    // are shown as Glyph::FALLBACK.
+    fn display_str(&mut self, text: &str) {
+        let mut digit_index = self.digits();
+        let mut dots = 0;
+        let mut characters = text.chars().peekable();
+
+        self.clear();
+        while digit_index > 0 {
+            let character = match characters.next() {
+                Some(character) => character,
+                None => break,
+            };
+            digit_index -= 1;
+
+            // a dot on its own takes a blank digit
+            let dot = character == '.' || characters.next_if_eq(&'.').is_some();
+            if character != '.' {
+                let glyph = Glyph::from_char(character).unwrap_or(Glyph::FALLBACK);
+                self.set_glyph(digit_index, Some(glyph));
+            }
+            if dot {
+                dots |= 1 << digit_index;
+            }
+        }
+        self.set_dots(dots);
+    }
+
+    // Decimal points, bit i for the digit i, kept until changed or cleared.
+    fn set_dots(&mut self, dots: u8);
+    // 0 to brightness::MAX_LEVEL, kept by clear.
+    fn set_brightness(&mut self, level: u8);
+    fn clear(&mut self);
+}
+
+// Text read a byte at a time, so the firmware can keep
+// the long texts in the program memory instead of the RAM.
+pub trait Text {
+    fn len(&self) -> usize;
+    fn byte(&self, index: usize) -> u8;
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Text for &str {
+    #[inline]
+    fn len(&self) -> usize {
+        str::len(self)
+    }
+
+    #[inline]
+    fn byte(&self, index: usize) -> u8 {
+        self.as_bytes()[index]
+    }
+}
+
+pub trait LEDMatrixDisplay {
+    fn width(&self) -> u8;
+    fn height(&self) -> u8;
+    fn get(&self, x: u8, y: u8) -> bool;
+
+    #[inline]
+    fn set(&mut self, x: u8, y: u8) {
+        self.set_led(x, y, framebuffer::Led::FULL);
+    }
+
+    // Lights the LED with its own intensity and blinking.
+    fn set_led(&mut self, x: u8, y: u8, led: framebuffer::Led);
+
+    // Lights all the LEDs with framebuffer::Led::FULL.
+    fn fill(&mut self) {
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                self.set(x, y);
+            }
+        }
+    }
+
+    // 0 to brightness::MAX_LEVEL, kept by clear.
+    fn set_brightness(&mut self, level: u8);
+    fn clear(&mut self);
+}
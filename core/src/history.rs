@@ -0,0 +1,69 @@
+// The last guesses of the running game with their marks,
+// so the player can look back at them.
+
+use super::scoring;
+
+// Guesses kept, the older ones are dropped.
+pub const SIZE: usize = 8;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Guess<const D: usize> {
+    pub number: [u8; D],
+    pub marks: [scoring::Mark; D], // the positions outside of the code are Absent
+}
+
+impl<const D: usize> Default for Guess<D> {
+    fn default() -> Guess<D> {
+        Guess {
+            number: [0; D],
+            marks: [scoring::Mark::Absent; D],
+        }
+    }
+}
+
+// A ring buffer, `next` is where the next guess goes.
+pub struct History<const D: usize> {
+    guesses: [Guess<D>; SIZE],
+    next: usize,
+    len: usize,
+}
+
+impl<const D: usize> History<D> {
+    pub fn create() -> History<D> {
+        History {
+            guesses: [Guess::default(); SIZE],
+            next: 0,
+            len: 0,
+        }
+    }
+
+    pub fn push(&mut self, guess: Guess<D>) {
+        self.guesses[self.next] = guess;
+        self.next = (self.next + 1) % SIZE;
+        self.len = (self.len + 1).min(SIZE);
+    }
+
+    // The guess `age` guesses back, 0 is the newest one.
+    pub fn get(&self, age: usize) -> Option<&Guess<D>> {
+        if age >= self.len {
+            return None;
+        }
+
+        Some(&self.guesses[(self.next + SIZE - 1 - age) % SIZE])
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.next = 0;
+        self.len = 0;
+    }
+}
@@ -0,0 +1,190 @@
+use super::brightness;
+use super::framebuffer;
+use super::hal;
+
+// W anodes for the columns, H cathodes for the rows, at most 8 of each.
+// The rows are scanned from the first one, a row at a time.
+pub struct LEDMatrix<O, const W: usize, const H: usize> {
+    frame: framebuffer::LEDMatrixFrame<W, H>,
+    anodes: [Option<O>; W],
+    cathodes: [Option<O>; H],
+    anodes_count: usize,
+    cathodes_count: usize,
+    blanking: brightness::Blanking,
+    // the phases of the blinking, taken once a millisecond
+    // so the scan does no division
+    slow_visible: bool,
+    fast_visible: bool,
+    row_ticks: [u8; W], // lit ticks of the LEDs of the shown row
+    update_step: usize
+}
+
+impl<O: hal::OutputPin, const W: usize, const H: usize> LEDMatrix<O, W, H> {
+    pub fn create() -> LEDMatrix<O, W, H> {
+        assert!(W <= 8 && H <= 8);
+
+        LEDMatrix {
+            frame: framebuffer::LEDMatrixFrame::create(),
+            anodes: [(); W].map(|_| None),
+            cathodes: [(); H].map(|_| None),
+            anodes_count: 0,
+            cathodes_count: 0,
+            blanking: brightness::Blanking::create(),
+            slow_visible: true,
+            fast_visible: true,
+            row_ticks: [0; W],
+            update_step: 0
+        }
+    }
+
+    // Shown from the next row on.
+    #[inline]
+    pub fn set_frame(&mut self, frame: framebuffer::LEDMatrixFrame<W, H>) {
+        self.frame = frame;
+        self.blanking.set_level(frame.brightness());
+    }
+
+    // The current time, the blinking LEDs follow it.
+    #[inline]
+    pub fn set_millis(&mut self, millis: u32) {
+        self.slow_visible = framebuffer::Blink::Slow.visible(millis);
+        self.fast_visible = framebuffer::Blink::Fast.visible(millis);
+    }
+
+    #[inline]
+    pub fn add_anode(&mut self, anode: O) {
+        self.anodes[self.anodes_count] = Some(anode);
+        self.anodes_count += 1;
+    }
+
+    #[inline]
+    pub fn add_cathode(&mut self, cathode: O) {
+        self.cathodes[self.cathodes_count] = Some(cathode);
+        self.cathodes_count += 1;
+    }
+
+    // A tick of the scan, every row is shown for brightness::SLOT_TICKS ticks
+    // and turned off once the lit part is over, the LEDs of a lower intensity
+    // are turned off sooner. Returns true after the last row.
+    pub fn step(&mut self) -> bool {
+        let tick = self.blanking.tick();
+        match self.blanking.step() {
+            brightness::Tick::Light => self.light_row(),
+            brightness::Tick::Blank => self.turn_off(),
+            brightness::Tick::Hold => self.dim_row(tick),
+        }
+
+        if !self.blanking.slot_ended() {
+            return false;
+        }
+
+        self.update_step += 1;
+        if self.update_step >= H {
+            self.update_step = 0;
+            return true;
+        }
+
+        return false;
+    }
+
+    fn turn_off(&mut self) {
+        for x in 0..self.cathodes_count {
+            if let Some(cathode) = &mut self.cathodes[x] {
+                cathode.set_high();
+            }
+        }
+    }
+
+    // Ticks of the slot the LED is lit, 0 when it is off or blinked out.
+    fn led_ticks(&self, x: usize) -> u8 {
+        let y: u8 = self.update_step.try_into().unwrap();
+        let x: u8 = x.try_into().unwrap();
+
+        match self.frame.led(x, y) {
+            Some(led) if self.visible(led.blink) => {
+                brightness::scaled_lit_ticks(self.frame.brightness(), led.intensity)
+            }
+            _ => 0,
+        }
+    }
+
+    #[inline]
+    fn visible(&self, blink: framebuffer::Blink) -> bool {
+        match blink {
+            framebuffer::Blink::Steady => true,
+            framebuffer::Blink::Slow => self.slow_visible,
+            framebuffer::Blink::Fast => self.fast_visible,
+        }
+    }
+
+    // The lit ticks are taken for the whole row here,
+    // the ticks after only compare them.
+    fn light_row(&mut self) {
+        self.turn_off();
+
+        let mut any_anode = false;
+        for x in 0..self.anodes_count {
+            self.row_ticks[x] = self.led_ticks(x);
+            let lit = self.row_ticks[x] > 0;
+            if let Some(anode) = &mut self.anodes[x] {
+                if lit {
+                    anode.set_high();
+                    any_anode = true;
+                } else {
+                    anode.set_low();
+                }
+            }
+        }
+
+        if any_anode {
+            if let Some(cathode) = &mut self.cathodes[self.update_step] {
+                cathode.set_low();
+            }
+        }
+    }
+
+    // Turns off the LEDs whose lit part ends at the tick.
+    fn dim_row(&mut self, tick: u8) {
+        for x in 0..self.anodes_count {
+            let ends = self.row_ticks[x] == tick;
+            if let Some(anode) = &mut self.anodes[x] {
+                if ends {
+                    anode.set_low();
+                }
+            }
+        }
+    }
+}
+
+impl<O: hal::OutputPin, const W: usize, const H: usize> hal::LEDMatrixDisplay for LEDMatrix<O, W, H> {
+    #[inline]
+    fn width(&self) -> u8 {
+        self.frame.width()
+    }
+
+    #[inline]
+    fn height(&self) -> u8 {
+        self.frame.height()
+    }
+
+    #[inline]
+    fn get(&self, x: u8, y: u8) -> bool {
+        self.frame.get(x, y)
+    }
+
+    #[inline]
+    fn set_led(&mut self, x: u8, y: u8, led: framebuffer::Led) {
+        self.frame.set_led(x, y, led);
+    }
+
+    #[inline]
+    fn set_brightness(&mut self, level: u8) {
+        self.frame.set_brightness(level);
+        self.blanking.set_level(self.frame.brightness());
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.frame.clear();
+    }
+}
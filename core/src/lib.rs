@@ -0,0 +1,34 @@
+#![no_std]
+// lpm reads the program memory
+#![cfg_attr(target_arch = "avr", feature(asm_experimental_arch))]
+// Written for the nightly toolchain pinned by the firmware,
+// so some newer lints are allowed.
+#![allow(
+    clippy::manual_is_multiple_of,
+    clippy::needless_return
+)]
+
+pub mod animation;
+pub mod brightness;
+pub mod button;
+pub mod clock;
+pub mod entropy;
+pub mod filled_seven_segment;
+pub mod filled_sipo;
+pub mod framebuffer;
+pub mod game;
+pub mod glyph;
+pub mod hal;
+pub mod history;
+pub mod led_matrix;
+pub mod mode;
+pub mod progmem;
+pub mod rng;
+pub mod rules;
+pub mod scores;
+pub mod scoring;
+pub mod settings;
+pub mod seven_segment;
+pub mod sipo;
+pub mod stats;
+pub mod storage;
@@ -0,0 +1,50 @@
+// Modes of the game, picked on the start screen before the game.
+
+use super::animation;
+use super::glyph::Glyph;
+
+const COUNTDOWN_LABEL: Glyph = Glyph::const_ascii(b'c');
+
+// Seconds of the time attack modes, they come after the classic mode.
+const TIME_LIMITS: [u16; 3] = [60, 120, 300];
+
+// classic, then a mode for each time limit
+pub const MODES: usize = 1 + TIME_LIMITS.len();
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum GameMode {
+    // as many attempts as the settings allow, no time limit
+    Classic,
+    // the code has to be cracked within the seconds
+    TimeAttack(u16),
+}
+
+impl GameMode {
+    // 0 is the classic mode, indexes past the last mode take the last one.
+    pub fn from_index(index: usize) -> GameMode {
+        match index {
+            0 => GameMode::Classic,
+            _ => GameMode::TimeAttack(TIME_LIMITS[(index - 1).min(TIME_LIMITS.len() - 1)]),
+        }
+    }
+
+    // In milliseconds, as the clock.
+    #[inline]
+    pub fn time_limit(self) -> Option<u32> {
+        match self {
+            GameMode::Classic => None,
+            GameMode::TimeAttack(seconds) => Some(u32::from(seconds) * 1000),
+        }
+    }
+}
+
+// What the seven segment display shows for the mode, the seconds
+// of the countdown after the label, the classic mode as dashes.
+pub fn page_digits<const D: usize>(mode: GameMode) -> [Option<Glyph>; D] {
+    let seconds = match mode {
+        GameMode::Classic => None,
+        GameMode::TimeAttack(seconds) => Some(u32::from(seconds)),
+    };
+
+    animation::labeled_digits(COUNTDOWN_LABEL, seconds)
+}
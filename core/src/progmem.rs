@@ -0,0 +1,42 @@
+// Bytes kept in the flash of the board instead of the RAM.
+// On the AVR the slice points to the program memory,
+// so it is only read through lpm, elsewhere it is a plain slice.
+
+use super::hal;
+
+pub struct ProgmemText(pub &'static [u8]);
+
+impl ProgmemText {
+    #[cfg(target_arch = "avr")]
+    #[inline]
+    fn read(&self, index: usize) -> u8 {
+        let address = self.0.as_ptr().wrapping_add(index);
+        let byte: u8;
+        unsafe {
+            core::arch::asm!("lpm {}, Z", out(reg) byte, in("Z") address);
+        }
+        byte
+    }
+
+    #[cfg(not(target_arch = "avr"))]
+    #[inline]
+    fn read(&self, index: usize) -> u8 {
+        self.0[index]
+    }
+}
+
+impl hal::Text for ProgmemText {
+    #[inline]
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    // 0 past the end.
+    fn byte(&self, index: usize) -> u8 {
+        if index >= self.0.len() {
+            return 0;
+        }
+
+        self.read(index)
+    }
+}
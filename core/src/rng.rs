@@ -0,0 +1,149 @@
+// Random number generators for the secrets.
+// The state of every generator fits into four bytes,
+// so it can be kept in the EEPROM between power cycles.
+
+pub trait Rng {
+    fn take_u8(&mut self) -> u8;
+
+    // Adds entropy to the state, see entropy::EntropyPool.
+    fn mix(&mut self, entropy: [u8; 4]);
+
+    // Four bytes to seed the generator with on the next boot.
+    fn next_seed(&mut self) -> [u8; 4] {
+        [self.take_u8(), self.take_u8(), self.take_u8(), self.take_u8()]
+    }
+
+    fn take_u16(&mut self) -> u16 {
+        let first: u16 = self.take_u8().into();
+        let second: u16 = self.take_u8().into();
+
+        first << 8 | second
+    }
+
+    // Uniform number in 0..n, n has to be at least 1.
+    // Values from the end of the u8 range
+    // that would make some results more likely are thrown away.
+    fn take_below(&mut self, n: u8) -> u8 {
+        debug_assert!(n > 0);
+        let limit = 256 - (256 % u16::from(n));
+
+        loop {
+            let value = self.take_u8();
+            if u16::from(value) < limit {
+                return value % n;
+            }
+        }
+    }
+
+    // Uniform number in low..=high, a high below low is taken as low.
+    fn take_range(&mut self, low: u8, high: u8) -> u8 {
+        let span = u16::from(high.saturating_sub(low)) + 1;
+
+        // the whole u8 range, nothing to throw away
+        if span > u16::from(u8::MAX) {
+            return self.take_u8();
+        }
+
+        low + self.take_below(span as u8)
+    }
+}
+
+// Marsaglia's xorshift with 32 bits of state,
+// goes through all 2^32 - 1 non zero states.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct XorShift32 {
+    state: u32,
+}
+
+// Used instead of a zero seed, zero is a fixed point of xorshift.
+const XORSHIFT_FALLBACK_SEED: u32 = 0x2D8B_7D8B;
+
+impl XorShift32 {
+    pub fn init(seed: [u8; 4]) -> XorShift32 {
+        let mut state = u32::from_le_bytes(seed);
+        if state == 0 {
+            state = XORSHIFT_FALLBACK_SEED;
+        }
+
+        XorShift32 {
+            state
+        }
+    }
+
+    fn randomize(&mut self) -> u32 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.state = x;
+
+        x
+    }
+}
+
+impl Rng for XorShift32 {
+    // The upper bits are the better mixed ones.
+    #[inline]
+    fn take_u8(&mut self) -> u8 {
+        (self.randomize() >> 24) as u8
+    }
+
+    fn mix(&mut self, entropy: [u8; 4]) {
+        self.state ^= u32::from_le_bytes(entropy);
+        if self.state == 0 {
+            self.state = XORSHIFT_FALLBACK_SEED;
+        }
+    }
+}
+
+// The original 8-bit generator of the game.
+// Its period depends on the seed and can be short,
+// kept to be able to compare.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LegacyRng {
+    a: u8,
+    b: u8,
+    c: u8,
+    x: u8
+}
+
+impl LegacyRng {
+    pub fn init(s1: u8, s2: u8, s3: u8) -> LegacyRng {
+        let mut rng = LegacyRng {
+            a: 0,
+            b: 0,
+            c: 0,
+            x: 0
+        };
+
+        rng.a ^= s1;
+        rng.b ^= s2;
+        rng.c ^= s3;
+        rng.randomize();
+
+        rng
+    }
+
+    fn randomize(&mut self) -> u8 {
+        self.x = self.x.wrapping_add(1);
+        self.a = self.a^self.c^self.x;
+        self.b = self.b.wrapping_add(self.a);
+        self.c = self.c.wrapping_add(self.b >> 1)^self.a;
+
+        self.c
+    }
+}
+
+impl Rng for LegacyRng {
+    #[inline]
+    fn take_u8(&mut self) -> u8 {
+        self.randomize()
+    }
+
+    fn mix(&mut self, entropy: [u8; 4]) {
+        self.a ^= entropy[0];
+        self.b ^= entropy[1];
+        self.c ^= entropy[2];
+        self.x ^= entropy[3];
+    }
+}
@@ -0,0 +1,74 @@
+use super::game::DIGITS;
+use super::rng;
+
+// Variants of the game.
+// The code takes the rightmost `length` digits of the display,
+// the leading digit is the leftmost of them.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct GameRules {
+    pub unique_digits: bool, // no digit repeats in the secret
+    pub leading_zero: bool, // the secret may start with 0
+    pub alphabet: u8, // digits go from 0 to alphabet - 1
+    pub length: u8, // number of digits of the secret
+}
+
+impl Default for GameRules {
+    fn default() -> GameRules {
+        GameRules {
+            unique_digits: false,
+            leading_zero: false,
+            alphabet: 10,
+            length: DIGITS as u8,
+        }
+    }
+}
+
+impl GameRules {
+    // Whether the rules can be played on the digits of the display.
+    pub fn valid(&self, digits: usize) -> bool {
+        let length: usize = self.length.into();
+        if length == 0 || length > digits {
+            return false;
+        }
+
+        if self.alphabet < 2 || self.alphabet > 10 {
+            return false;
+        }
+
+        if self.unique_digits && self.alphabet < self.length {
+            return false;
+        }
+
+        return true;
+    }
+
+    #[inline]
+    pub fn length(&self) -> usize {
+        self.length.into()
+    }
+
+    // Digits on positions outside of the code are left at 0.
+    pub fn generate_number<R: rng::Rng, const D: usize>(&self, rng: &mut R) -> [u8; D] {
+        let mut digits = [0; D];
+        let length = self.length();
+
+        for i in (0..length).rev() {
+            let leading = i == length - 1;
+
+            loop {
+                let digit = if leading && !self.leading_zero {
+                    rng.take_range(1, self.alphabet - 1)
+                } else {
+                    rng.take_below(self.alphabet)
+                };
+
+                if !self.unique_digits || !digits[i + 1..length].contains(&digit) {
+                    digits[i] = digit;
+                    break;
+                }
+            }
+        }
+
+        digits
+    }
+}
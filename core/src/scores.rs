@@ -0,0 +1,36 @@
+// Best results over all the games, kept in the storage.
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct HighScores {
+    pub best_attempts: Option<u16>, // fewest guesses of a won game
+    pub best_time: Option<u32>, // shortest won game in milliseconds
+}
+
+impl HighScores {
+    // Returns whether the result is a new high score.
+    pub fn record_attempts(&mut self, attempts: u16) -> bool {
+        match self.best_attempts {
+            Some(best) if best <= attempts => false,
+            _ => {
+                self.best_attempts = Some(attempts);
+                true
+            }
+        }
+    }
+
+    // Returns whether the result is a new high score.
+    pub fn record_time(&mut self, time: u32) -> bool {
+        match self.best_time {
+            Some(best) if best <= time => false,
+            _ => {
+                self.best_time = Some(time);
+                true
+            }
+        }
+    }
+
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = HighScores::default();
+    }
+}
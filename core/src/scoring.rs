@@ -0,0 +1,40 @@
+// Scoring of a guess against the secret, as in Mastermind.
+// Every digit of the secret is matched at most once,
+// exact matches first, then the remaining digits
+// of the guess in the order of their positions.
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Mark {
+    Absent, // the digit is not in the secret, or all its occurrences are matched already
+    Misplaced, // the digit is in the secret on another position
+    Correct, // the digit is on the correct position
+}
+
+// Fills in a mark for every position of the guess.
+// Secret, guess and marks have to be of the same length, at most 16.
+pub fn score(secret: &[u8], guess: &[u8], marks: &mut [Mark]) {
+    let mut matched: u16 = 0;
+
+    for i in 0..guess.len() {
+        if guess[i] == secret[i] {
+            marks[i] = Mark::Correct;
+            matched |= 1 << i;
+        } else {
+            marks[i] = Mark::Absent;
+        }
+    }
+
+    for i in 0..guess.len() {
+        if marks[i] == Mark::Correct {
+            continue;
+        }
+
+        for (j, secret_digit) in secret.iter().enumerate() {
+            if matched & (1 << j) == 0 && guess[i] == *secret_digit {
+                marks[i] = Mark::Misplaced;
+                matched |= 1 << j;
+                break;
+            }
+        }
+    }
+}
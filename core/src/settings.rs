@@ -0,0 +1,156 @@
+// Settings of the board, changed in the settings menu
+// and kept in the storage.
+
+use super::animation;
+use super::brightness;
+use super::glyph::Glyph;
+use super::rules;
+
+const SEVEN_SEGMENT_BRIGHTNESS_LABEL: Glyph = Glyph::const_ascii(b'd'); // digits
+const LED_MATRIX_BRIGHTNESS_LABEL: Glyph = Glyph::const_ascii(b'L'); // LEDs
+const INPUT_MODE_LABEL: Glyph = Glyph::const_ascii(b'E'); // entry
+const MAX_ATTEMPTS_LABEL: Glyph = Glyph::const_ascii(b't'); // tries, as the attempts of a won game
+const LENGTH_LABEL: Glyph = Glyph::const_ascii(b'n'); // number of digits
+const ALPHABET_LABEL: Glyph = Glyph::const_ascii(b'A');
+const UNIQUE_DIGITS_LABEL: Glyph = Glyph::const_ascii(b'U');
+const LEADING_ZERO_LABEL: Glyph = Glyph::const_ascii(b'o'); // zero
+
+// The most digits a game takes, the display is checked by the game.
+const MAX_LENGTH: u8 = 8;
+const MAX_ALPHABET: u8 = 10;
+
+// The largest attempt limit, it fits the digits after the label.
+pub const MAX_ATTEMPTS_LIMIT: u8 = 99;
+
+// seven segment brightness, LED matrix brightness, input mode, attempt limit,
+// then the rules, length, alphabet, unique digits and leading zero
+pub const PAGES: usize = 8;
+
+// What the digit buttons do while a game is played.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum InputMode {
+    // each button counts its digit up, a double click counts down
+    Count,
+    // as Count, with confirm held the buttons count down
+    Shift,
+    // the left half of the buttons moves a cursor, the right half
+    // counts the digit under it down and up
+    Cursor,
+}
+
+impl InputMode {
+    const LAST: InputMode = InputMode::Cursor;
+
+    // None for the bytes of no mode, from a damaged storage.
+    pub fn from_byte(byte: u8) -> Option<InputMode> {
+        match byte {
+            0 => Some(InputMode::Count),
+            1 => Some(InputMode::Shift),
+            2 => Some(InputMode::Cursor),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn to_byte(self) -> u8 {
+        self as u8
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Settings {
+    pub seven_segment_brightness: u8, // 0 to brightness::MAX_LEVEL
+    pub led_matrix_brightness: u8,
+    pub input_mode: InputMode,
+    pub max_attempts: u8, // the game is lost after as many wrong guesses, 0 is no limit
+    pub rules: rules::GameRules, // of the next game
+}
+
+impl Default for Settings {
+    fn default() -> Settings {
+        Settings {
+            seven_segment_brightness: brightness::MAX_LEVEL,
+            led_matrix_brightness: brightness::MAX_LEVEL,
+            input_mode: InputMode::Count,
+            max_attempts: 0,
+            rules: rules::GameRules::default(),
+        }
+    }
+}
+
+impl Settings {
+    // Values out of range, from a damaged storage, are taken as the defaults.
+    pub fn sanitized(self) -> Settings {
+        let defaults = Settings::default();
+        let level = |level: u8, default: u8| if level <= brightness::MAX_LEVEL { level } else { default };
+
+        Settings {
+            seven_segment_brightness: level(self.seven_segment_brightness, defaults.seven_segment_brightness),
+            led_matrix_brightness: level(self.led_matrix_brightness, defaults.led_matrix_brightness),
+            input_mode: self.input_mode,
+            max_attempts: self.max_attempts.min(MAX_ATTEMPTS_LIMIT),
+            rules: if self.rules.valid(usize::from(MAX_LENGTH)) { self.rules } else { defaults.rules },
+        }
+    }
+
+    // Moves the value of the page up or down by one, stops at the ends.
+    // Returns whether it changed. The rules may end up invalid,
+    // the game checks them against its digits.
+    pub fn change(&mut self, page: usize, up: bool) -> bool {
+        let (value, max) = match page {
+            0 => (&mut self.seven_segment_brightness, brightness::MAX_LEVEL),
+            1 => (&mut self.led_matrix_brightness, brightness::MAX_LEVEL),
+            2 => {
+                let mut mode = self.input_mode.to_byte();
+                let is_changed = Settings::step(&mut mode, InputMode::LAST.to_byte(), up);
+                self.input_mode = InputMode::from_byte(mode).unwrap_or(self.input_mode);
+                return is_changed;
+            }
+            3 => (&mut self.max_attempts, MAX_ATTEMPTS_LIMIT),
+            4 => (&mut self.rules.length, MAX_LENGTH),
+            5 => (&mut self.rules.alphabet, MAX_ALPHABET),
+            6 | 7 => {
+                let flag = if page == 6 { &mut self.rules.unique_digits } else { &mut self.rules.leading_zero };
+                let mut value = u8::from(*flag);
+                let is_changed = Settings::step(&mut value, 1, up);
+                *flag = value == 1;
+                return is_changed;
+            }
+            _ => return false,
+        };
+
+        Settings::step(value, max, up)
+    }
+
+    // Moves the value by one within 0 to max, returns whether it changed.
+    fn step(value: &mut u8, max: u8, up: bool) -> bool {
+        let changed = if up {
+            (*value + 1).min(max)
+        } else {
+            value.saturating_sub(1)
+        };
+
+        let is_changed = changed != *value;
+        *value = changed;
+        is_changed
+    }
+}
+
+// What the seven segment display shows on the page,
+// a label on the leftmost digit and the value,
+// brightness levels and input modes are shown from 1,
+// no attempt limit as dashes, the rules that are on or off as 1 or 0.
+pub fn page_digits<const D: usize>(page: usize, settings: &Settings) -> [Option<Glyph>; D] {
+    let (label, value) = match page {
+        0 => (SEVEN_SEGMENT_BRIGHTNESS_LABEL, Some(settings.seven_segment_brightness + 1)),
+        1 => (LED_MATRIX_BRIGHTNESS_LABEL, Some(settings.led_matrix_brightness + 1)),
+        2 => (INPUT_MODE_LABEL, Some(settings.input_mode.to_byte() + 1)),
+        3 => (MAX_ATTEMPTS_LABEL, Some(settings.max_attempts).filter(|limit| *limit != 0)),
+        4 => (LENGTH_LABEL, Some(settings.rules.length)),
+        5 => (ALPHABET_LABEL, Some(settings.rules.alphabet)),
+        6 => (UNIQUE_DIGITS_LABEL, Some(u8::from(settings.rules.unique_digits))),
+        _ => (LEADING_ZERO_LABEL, Some(u8::from(settings.rules.leading_zero))),
+    };
+
+    animation::labeled_digits(label, value.map(u32::from))
+}
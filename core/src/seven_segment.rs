@@ -0,0 +1,72 @@
+use super::filled_sipo;
+use super::glyph::Glyph;
+use super::hal;
+
+// D digits driven through chained shift registers,
+// the digit selectors first, the segments in the last byte.
+pub struct SevenSegment<const D: usize> {
+    dp: bool,
+    common_cathode: bool
+}
+
+impl<const D: usize> SevenSegment<D> {
+    pub fn create(dp: bool, common_cathode: bool) -> SevenSegment<D> {
+        SevenSegment {
+            dp,
+            common_cathode
+        }
+    }
+
+    #[inline]
+    pub fn digits(&self) -> usize {
+        D
+    }
+
+    #[inline]
+    pub fn dp(&self) -> bool {
+        return self.dp;
+    }
+
+    #[inline]
+    pub fn common_cathode(&self) -> bool {
+        return self.common_cathode;
+    }
+
+    pub fn fill_glyph<O: hal::OutputPin, const N: usize>(&self, sipo: &mut filled_sipo::FilledSipo<O, N>, glyph: Glyph, digit_index: usize) -> bool {
+        self.fill_segments(sipo, glyph.segments(), digit_index)
+    }
+
+    // Segments as in the Glyph, H is the decimal point.
+    // Without the decimal point it is ignored and the digit selectors
+    // take the freed bit. The selectors take the rest of the N bytes.
+    pub fn fill_segments<O: hal::OutputPin, const N: usize>(&self, sipo: &mut filled_sipo::FilledSipo<O, N>, segments: u8, digit_index: usize) -> bool {
+        if digit_index >= D {
+            return false;
+        }
+
+        let segment_bits = if self.dp { 8 } else { 7 };
+        let selector_mask: u32 = (1 << (8 * N - segment_bits)) - 1;
+
+        let mut segments = segments;
+        let mut digit_selector: u32 = 1 << (D - 1 - digit_index);
+
+        if self.common_cathode {
+            digit_selector = !digit_selector & selector_mask;
+        } else {
+            segments = !segments;
+        }
+
+        if !self.dp {
+            segments &= 0x7F;
+        }
+
+        let value = (digit_selector << segment_bits) | u32::from(segments);
+        let mut data = [0; N];
+        for (i, byte) in data.iter_mut().rev().enumerate() {
+            *byte = (value >> (8 * i)) as u8;
+        }
+
+        sipo.set_data(data);
+        return true;
+    }
+}
@@ -1,14 +1,14 @@
-use atmega_hal::port::{Pin, mode};
+use super::hal;
 
-pub struct Sipo {
-    srclk : Pin<mode::Output>,
-    srclr : Pin<mode::Output>,
-    ser : Pin<mode::Output>,
-    rclk : Pin<mode::Output>,
+pub struct Sipo<O> {
+    srclk : O,
+    srclr : O,
+    ser : O,
+    rclk : O,
 }
 
-impl Sipo {
-    pub fn create(srclk: Pin<mode::Output>, srclr: Pin<mode::Output>, ser: Pin<mode::Output>, rclk: Pin<mode::Output>) -> Sipo {
+impl<O: hal::OutputPin> Sipo<O> {
+    pub fn create(srclk: O, srclr: O, ser: O, rclk: O) -> Sipo<O> {
         let mut sipo = Sipo {
             srclk,
             srclr,
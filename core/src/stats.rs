@@ -0,0 +1,79 @@
+// Lifetime statistics of the board, kept in the storage,
+// and the pages of the statistics screen.
+
+use super::animation;
+use super::glyph::Glyph;
+use super::scores;
+
+// Wins in 1 to 8 attempts, the last bucket counts 9 attempts and more.
+pub const HISTOGRAM_BUCKETS: usize = 9;
+
+const PLAYED_LABEL: Glyph = Glyph::const_ascii(b'P');
+const WON_LABEL: Glyph = Glyph::const_ascii(b'G'); // guessed
+const ABANDONED_LABEL: Glyph = Glyph::const_ascii(b'A');
+const LOST_LABEL: Glyph = Glyph::const_ascii(b'L');
+
+// best attempts, best time, played, won, abandoned, lost, then the histogram
+pub const PAGES: usize = 6 + HISTOGRAM_BUCKETS;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct Statistics {
+    pub played: u32, // started games
+    pub won: u32,
+    pub lost: u32, // out of attempts or time
+    pub histogram: [u16; HISTOGRAM_BUCKETS], // won games by attempts
+}
+
+impl Statistics {
+    #[inline]
+    pub fn record_start(&mut self) {
+        self.played = self.played.saturating_add(1);
+    }
+
+    pub fn record_win(&mut self, attempts: u16) {
+        self.won = self.won.saturating_add(1);
+
+        let bucket = Self::bucket(attempts);
+        self.histogram[bucket] = self.histogram[bucket].saturating_add(1);
+    }
+
+    #[inline]
+    pub fn record_loss(&mut self) {
+        self.lost = self.lost.saturating_add(1);
+    }
+
+    // Games started but neither won nor lost, given up
+    // or the board was turned off during them.
+    #[inline]
+    pub fn abandoned(&self) -> u32 {
+        self.played.saturating_sub(self.won).saturating_sub(self.lost)
+    }
+
+    #[inline]
+    pub fn bucket(attempts: u16) -> usize {
+        usize::from(attempts.max(1) - 1).min(HISTOGRAM_BUCKETS - 1)
+    }
+}
+
+// What the seven segment display shows on the page, digits and dots,
+// a label on the leftmost digit and the value.
+// The best time is MM.SS without a label.
+// The histogram pages are labeled with the attempts,
+// "3 12" are 12 games won in 3 attempts, 9 stands for 9 and more.
+pub fn page_digits<const D: usize>(page: usize, high_scores: &scores::HighScores, statistics: &Statistics) -> ([Option<Glyph>; D], u8) {
+    let digits = match page {
+        0 => animation::best_attempts_digits(high_scores.best_attempts),
+        1 => return (animation::time_digits(high_scores.best_time), animation::TIME_DOTS),
+        2 => animation::labeled_digits(PLAYED_LABEL, Some(statistics.played)),
+        3 => animation::labeled_digits(WON_LABEL, Some(statistics.won)),
+        4 => animation::labeled_digits(ABANDONED_LABEL, Some(statistics.abandoned())),
+        5 => animation::labeled_digits(LOST_LABEL, Some(statistics.lost)),
+        _ => {
+            let bucket = (page - 6).min(HISTOGRAM_BUCKETS - 1);
+            let label = Glyph::digit(bucket as u8 + 1);
+            animation::labeled_digits(label, Some(u32::from(statistics.histogram[bucket])))
+        }
+    };
+
+    (digits, 0)
+}
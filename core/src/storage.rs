@@ -0,0 +1,291 @@
+// Persistent data of the game in the EEPROM.
+//
+// The EEPROM is split into slots, every save goes to the slot after
+// the newest one, so the writes are spread over the whole EEPROM.
+// A slot holds
+//   magic, version, sequence number, payload, CRC-16 of all of it.
+// On load the valid slot with the newest sequence number wins.
+// If there is none, the bare seed of the older firmware
+// at the start of the EEPROM is taken over.
+
+use super::rules;
+use super::scores;
+use super::settings;
+use super::stats;
+
+pub trait Eeprom {
+    fn capacity(&self) -> u16;
+    fn read(&self, offset: u16, buffer: &mut [u8]);
+    fn write(&mut self, offset: u16, data: &[u8]);
+}
+
+pub const SLOT_SIZE: usize = 64;
+const HEADER_SIZE: usize = 3;
+const CRC_SIZE: usize = 2;
+pub const PAYLOAD_SIZE: usize = SLOT_SIZE - HEADER_SIZE - CRC_SIZE;
+
+const MAGIC: u8 = 0xA5;
+// 1: seed
+// 2: high scores
+// 3: statistics
+// 4: settings
+// 5: input mode
+// 6: attempt limit, lost games
+// 7: rules
+pub const VERSION: u8 = 7;
+
+const LEGACY_SEED_OFFSET: u16 = 0;
+const DEFAULT_SEED: [u8; 4] = [125, 139, 45, 0];
+
+// Stored instead of a missing value.
+const NONE_U16: u16 = 0xFFFF;
+const NONE_U32: u32 = 0xFFFF_FFFF;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Record {
+    pub seed: [u8; 4],
+    pub high_scores: scores::HighScores,
+    pub statistics: stats::Statistics,
+    pub settings: settings::Settings,
+}
+
+impl Default for Record {
+    fn default() -> Record {
+        Record {
+            seed: DEFAULT_SEED,
+            high_scores: scores::HighScores::default(),
+            statistics: stats::Statistics::default(),
+            settings: settings::Settings::default(),
+        }
+    }
+}
+
+impl Record {
+    // New fields go after the existing ones, so the older
+    // versions can be read, the missing fields get defaults.
+    fn encode(&self, payload: &mut [u8; PAYLOAD_SIZE]) {
+        payload[0..4].copy_from_slice(&self.seed);
+
+        let best_attempts = self.high_scores.best_attempts.unwrap_or(NONE_U16);
+        payload[4..6].copy_from_slice(&best_attempts.to_le_bytes());
+        let best_time = self.high_scores.best_time.unwrap_or(NONE_U32);
+        payload[6..10].copy_from_slice(&best_time.to_le_bytes());
+
+        payload[10..14].copy_from_slice(&self.statistics.played.to_le_bytes());
+        payload[14..18].copy_from_slice(&self.statistics.won.to_le_bytes());
+        for (i, count) in self.statistics.histogram.iter().enumerate() {
+            let offset = 18 + 2 * i;
+            payload[offset..offset + 2].copy_from_slice(&count.to_le_bytes());
+        }
+
+        payload[36] = self.settings.seven_segment_brightness;
+        payload[37] = self.settings.led_matrix_brightness;
+        payload[38] = self.settings.input_mode.to_byte();
+
+        payload[39] = self.settings.max_attempts;
+        payload[40..44].copy_from_slice(&self.statistics.lost.to_le_bytes());
+
+        let rules = self.settings.rules;
+        payload[44] = rules.length;
+        payload[45] = rules.alphabet;
+        payload[46] = u8::from(rules.unique_digits) | u8::from(rules.leading_zero) << 1;
+    }
+
+    fn decode(version: u8, payload: &[u8; PAYLOAD_SIZE]) -> Record {
+        let mut record = Record::default();
+        record.seed.copy_from_slice(&payload[0..4]);
+
+        if version >= 2 {
+            let best_attempts = u16::from_le_bytes([payload[4], payload[5]]);
+            record.high_scores.best_attempts = Some(best_attempts).filter(|value| *value != NONE_U16);
+            let best_time = u32::from_le_bytes([payload[6], payload[7], payload[8], payload[9]]);
+            record.high_scores.best_time = Some(best_time).filter(|value| *value != NONE_U32);
+        }
+
+        if version >= 3 {
+            record.statistics.played = u32::from_le_bytes([payload[10], payload[11], payload[12], payload[13]]);
+            record.statistics.won = u32::from_le_bytes([payload[14], payload[15], payload[16], payload[17]]);
+            for (i, count) in record.statistics.histogram.iter_mut().enumerate() {
+                let offset = 18 + 2 * i;
+                *count = u16::from_le_bytes([payload[offset], payload[offset + 1]]);
+            }
+        }
+
+        if version >= 4 {
+            record.settings = settings::Settings {
+                seven_segment_brightness: payload[36],
+                led_matrix_brightness: payload[37],
+                ..settings::Settings::default()
+            }.sanitized();
+        }
+
+        if version >= 5 {
+            if let Some(input_mode) = settings::InputMode::from_byte(payload[38]) {
+                record.settings.input_mode = input_mode;
+            }
+        }
+
+        if version >= 6 {
+            record.settings.max_attempts = payload[39];
+            record.settings = record.settings.sanitized();
+            record.statistics.lost = u32::from_le_bytes([payload[40], payload[41], payload[42], payload[43]]);
+        }
+
+        if version >= 7 {
+            record.settings.rules = rules::GameRules {
+                length: payload[44],
+                alphabet: payload[45],
+                unique_digits: payload[46] & 1 != 0,
+                leading_zero: payload[46] & 2 != 0,
+            };
+            record.settings = record.settings.sanitized();
+        }
+
+        record
+    }
+}
+
+pub struct Storage<E> {
+    eeprom: E,
+    slots: u8,
+    slot: Option<u8>, // the newest valid slot
+    sequence: u8,
+}
+
+impl<E: Eeprom> Storage<E> {
+    pub fn create(eeprom: E) -> Storage<E> {
+        let slots = (usize::from(eeprom.capacity()) / SLOT_SIZE) as u8;
+
+        Storage {
+            eeprom,
+            slots,
+            slot: None,
+            sequence: 0,
+        }
+    }
+
+    // Gives the EEPROM back.
+    pub fn release(self) -> E {
+        self.eeprom
+    }
+
+    pub fn load(&mut self) -> Record {
+        let mut newest: Option<(u8, u8, Record)> = None;
+
+        for slot in 0..self.slots {
+            if let Some((sequence, record)) = self.read_slot(slot) {
+                let is_newer = match newest {
+                    Some((_, newest_sequence, _)) => Self::is_newer(sequence, newest_sequence),
+                    None => true,
+                };
+
+                if is_newer {
+                    newest = Some((slot, sequence, record));
+                }
+            }
+        }
+
+        match newest {
+            Some((slot, sequence, record)) => {
+                self.slot = Some(slot);
+                self.sequence = sequence;
+                record
+            }
+            None => {
+                self.slot = None;
+                self.sequence = 0;
+                self.migrate()
+            }
+        }
+    }
+
+    pub fn save(&mut self, record: &Record) {
+        if self.slots == 0 {
+            return;
+        }
+
+        let (slot, sequence) = match self.slot {
+            Some(slot) => ((slot + 1) % self.slots, self.sequence.wrapping_add(1)),
+            None => (0, 0),
+        };
+
+        let mut data = [0u8; SLOT_SIZE];
+        data[0] = MAGIC;
+        data[1] = VERSION;
+        data[2] = sequence;
+
+        let mut payload = [0u8; PAYLOAD_SIZE];
+        record.encode(&mut payload);
+        data[HEADER_SIZE..HEADER_SIZE + PAYLOAD_SIZE].copy_from_slice(&payload);
+
+        let crc = crc16(&data[..SLOT_SIZE - CRC_SIZE]);
+        data[SLOT_SIZE - CRC_SIZE..].copy_from_slice(&crc.to_le_bytes());
+
+        self.eeprom.write(Self::slot_offset(slot), &data);
+        self.slot = Some(slot);
+        self.sequence = sequence;
+    }
+
+    fn read_slot(&self, slot: u8) -> Option<(u8, Record)> {
+        let mut data = [0u8; SLOT_SIZE];
+        self.eeprom.read(Self::slot_offset(slot), &mut data);
+
+        if data[0] != MAGIC || data[1] == 0 || data[1] > VERSION {
+            return None;
+        }
+
+        let crc = u16::from_le_bytes([data[SLOT_SIZE - 2], data[SLOT_SIZE - 1]]);
+        if crc != crc16(&data[..SLOT_SIZE - CRC_SIZE]) {
+            return None;
+        }
+
+        let mut payload = [0u8; PAYLOAD_SIZE];
+        payload.copy_from_slice(&data[HEADER_SIZE..HEADER_SIZE + PAYLOAD_SIZE]);
+
+        Some((data[2], Record::decode(data[1], &payload)))
+    }
+
+    // The firmware before the storage kept only the seed of the generator
+    // at the start of the EEPROM.
+    fn migrate(&self) -> Record {
+        let mut seed = [0u8; 4];
+        self.eeprom.read(LEGACY_SEED_OFFSET, &mut seed);
+
+        let mut record = Record::default();
+        // both erased and never written EEPROM are no seed
+        if seed != [0x00; 4] && seed != [0xFF; 4] {
+            record.seed = seed;
+        }
+
+        record
+    }
+
+    #[inline]
+    fn slot_offset(slot: u8) -> u16 {
+        u16::from(slot) * SLOT_SIZE as u16
+    }
+
+    // Sequence numbers wrap, the newer one is at most half of the range ahead.
+    #[inline]
+    fn is_newer(sequence: u8, than: u8) -> bool {
+        (sequence.wrapping_sub(than) as i8) > 0
+    }
+}
+
+// CRC-16/CCITT-FALSE, computed bit by bit to keep the flash usage low.
+pub fn crc16(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0xFFFF;
+
+    for byte in data {
+        crc ^= u16::from(*byte) << 8;
+        for _ in 0..8 {
+            if crc & 0x8000 != 0 {
+                crc = (crc << 1) ^ 0x1021;
+            } else {
+                crc <<= 1;
+            }
+        }
+    }
+
+    crc
+}
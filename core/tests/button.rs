@@ -0,0 +1,112 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use guess_the_number_core::button::{self, Button, ButtonEvent};
+use guess_the_number_core::hal::InputPin;
+
+#[derive(Clone, Default)]
+struct MockInput(Rc<Cell<bool>>);
+
+impl InputPin for MockInput {
+    fn is_low(&self) -> bool {
+        self.0.get()
+    }
+}
+
+// Steps a button through the trace of held or released stretches,
+// the events are returned with the step they came on, counted from 0.
+fn events(trace: &[(bool, u32)]) -> Vec<(u32, ButtonEvent)> {
+    let input = MockInput::default();
+    let mut button = Button::create(input.clone(), false);
+    let mut events = Vec::new();
+    let mut step = 0;
+
+    for (held, steps) in trace {
+        input.0.set(*held);
+        for _ in 0..*steps {
+            button.step();
+            let event = button.event();
+            if event != ButtonEvent::None {
+                events.push((step, event));
+            }
+            step += 1;
+        }
+    }
+
+    events
+}
+
+// Steps until a change of the input shows, the debounce.
+const SETTLE: u32 = button::DEBOUNCECYCLES as u32 - 1;
+const LONG: u32 = button::PRESSED_FOR_LONG as u32;
+const REPEAT: u32 = button::PRESSED_FOR_REPEAT as u32;
+const DOUBLE_CLICK: u32 = button::RELEASED_FOR_DOUBLE_CLICK as u32;
+
+#[test]
+fn click_waits_for_the_double_click_window() {
+    assert_eq!(
+        events(&[(true, 500), (false, 4000)]),
+        [(500 + SETTLE + DOUBLE_CLICK - 1, ButtonEvent::Click)]
+    );
+}
+
+#[test]
+fn second_press_is_a_double_click_at_once() {
+    // the click still comes after the release
+    assert_eq!(
+        events(&[(true, 500), (false, 500), (true, 500), (false, 4000)]),
+        [
+            (1000 + SETTLE, ButtonEvent::DoubleClick),
+            (1500 + SETTLE + DOUBLE_CLICK - 1, ButtonEvent::Click),
+        ]
+    );
+
+    // too late for a double click
+    let events = events(&[(true, 500), (false, DOUBLE_CLICK + 500), (true, 500), (false, 4000)]);
+    assert!(events.iter().all(|(_, event)| *event == ButtonEvent::Click));
+    assert_eq!(events.len(), 2);
+}
+
+#[test]
+fn held_button_repeats_after_the_long_click() {
+    let events = events(&[(true, SETTLE + LONG + 3 * REPEAT), (false, 4000)]);
+    assert_eq!(
+        events,
+        [
+            (SETTLE + LONG - 1, ButtonEvent::LongClick),
+            (SETTLE + LONG + REPEAT - 1, ButtonEvent::LongClickContinuous),
+            (SETTLE + LONG + 2 * REPEAT - 1, ButtonEvent::LongClickContinuous),
+            (SETTLE + LONG + 3 * REPEAT - 1, ButtonEvent::LongClickContinuous),
+        ]
+    );
+}
+
+#[test]
+fn repeating_does_not_stop() {
+    let events = events(&[(true, 80_000)]);
+    let last = events.last().unwrap().0;
+    assert!(last > 80_000 - REPEAT, "{}", last);
+
+    for pair in events.windows(2) {
+        assert_eq!(pair[1].0 - pair[0].0, REPEAT);
+    }
+}
+
+#[test]
+fn bounces_are_ignored() {
+    assert_eq!(events(&[(true, SETTLE), (false, 4000)]), []);
+
+    // short drops while held do not release the button
+    let mut trace = vec![(false, 100)];
+    for _ in 0..80 {
+        trace.push((true, 60));
+        trace.push((false, 20));
+    }
+    trace.push((false, 4000));
+    assert_eq!(events(&trace).iter().map(|(_, event)| *event).collect::<Vec<_>>(), [ButtonEvent::LongClick]);
+}
+
+#[test]
+fn first_press_is_not_a_double_click() {
+    assert_eq!(events(&[(true, 100), (false, 4000)])[0].1, ButtonEvent::Click);
+}
@@ -0,0 +1,36 @@
+use guess_the_number_core::clock::Stopwatch;
+
+#[test]
+fn measures_from_start_to_stop() {
+    let mut stopwatch = Stopwatch::create();
+    assert_eq!(stopwatch.elapsed(100), 0);
+
+    stopwatch.start(1_000);
+    assert!(stopwatch.running());
+    assert_eq!(stopwatch.elapsed(1_500), 500);
+
+    assert_eq!(stopwatch.stop(4_000), 3_000);
+    assert!(!stopwatch.running());
+    assert_eq!(stopwatch.elapsed(9_000), 3_000);
+
+    // stopping again keeps the time
+    assert_eq!(stopwatch.stop(10_000), 3_000);
+}
+
+#[test]
+fn measures_over_clock_wrap() {
+    let mut stopwatch = Stopwatch::create();
+    stopwatch.start(u32::MAX - 99);
+
+    assert_eq!(stopwatch.stop(400), 500);
+}
+
+#[test]
+fn restart_forgets_the_last_time() {
+    let mut stopwatch = Stopwatch::create();
+    stopwatch.start(0);
+    stopwatch.stop(700);
+
+    stopwatch.start(1_000);
+    assert_eq!(stopwatch.elapsed(1_200), 200);
+}
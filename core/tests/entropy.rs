@@ -0,0 +1,97 @@
+use guess_the_number_core::entropy::{self, EntropyPool, EntropySource};
+use guess_the_number_core::rng::{self, Rng};
+
+// Replays recorded readings instead of the hardware.
+struct ScriptedSource {
+    samples: Vec<u16>,
+    position: usize,
+}
+
+impl ScriptedSource {
+    fn create(samples: &[u16]) -> ScriptedSource {
+        ScriptedSource {
+            samples: samples.to_vec(),
+            position: 0,
+        }
+    }
+}
+
+impl EntropySource for ScriptedSource {
+    fn sample(&mut self) -> u16 {
+        let sample = self.samples[self.position % self.samples.len()];
+        self.position += 1;
+        sample
+    }
+}
+
+fn pool_seed(samples: &[u16]) -> [u8; 4] {
+    let mut pool = EntropyPool::create();
+    pool.collect(&mut ScriptedSource::create(samples), samples.len() as u8);
+    pool.seed()
+}
+
+#[test]
+fn same_samples_give_same_seed() {
+    let samples = [512, 513, 511, 512, 514, 512, 510, 513];
+
+    assert_eq!(pool_seed(&samples), pool_seed(&samples));
+}
+
+#[test]
+fn every_sample_matters() {
+    let samples = [512, 513, 511, 512, 514, 512, 510, 513];
+    let seed = pool_seed(&samples);
+
+    for i in 0..samples.len() {
+        let mut changed = samples;
+        changed[i] ^= 1;
+        assert_ne!(pool_seed(&changed), seed, "sample {}", i);
+    }
+}
+
+#[test]
+fn order_of_samples_matters() {
+    assert_ne!(pool_seed(&[1, 2]), pool_seed(&[2, 1]));
+}
+
+#[test]
+fn collect_takes_requested_samples() {
+    let mut source = ScriptedSource::create(&[1, 2, 3]);
+    let mut pool = EntropyPool::create();
+
+    pool.collect(&mut source, 5);
+
+    assert_eq!(source.position, 5);
+}
+
+#[test]
+fn spread_changes_about_half_of_the_bits() {
+    for bit in 0..32 {
+        let flipped: u32 = (0..1000u32)
+            .map(|value| (entropy::spread(value) ^ entropy::spread(value ^ (1 << bit))).count_ones())
+            .sum();
+        let average = f64::from(flipped) / 1000.0;
+
+        assert!((average - 16.0).abs() < 0.5, "bit {} flips {} bits on average", bit, average);
+    }
+}
+
+#[test]
+fn mixing_changes_the_sequence() {
+    let seed = [125, 139, 45, 0];
+    let mut plain = rng::XorShift32::init(seed);
+    let mut mixed = rng::XorShift32::init(seed);
+    mixed.mix(pool_seed(&[512, 513, 511]));
+
+    let plain: Vec<u8> = (0..16).map(|_| plain.take_u8()).collect();
+    let mixed: Vec<u8> = (0..16).map(|_| mixed.take_u8()).collect();
+    assert_ne!(plain, mixed);
+}
+
+#[test]
+fn mixing_never_zeroes_xorshift() {
+    let mut rng = rng::XorShift32::init([1, 0, 0, 0]);
+    rng.mix([1, 0, 0, 0]);
+
+    assert!((0..16).any(|_| rng.take_u8() != 0));
+}
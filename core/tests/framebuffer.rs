@@ -0,0 +1,103 @@
+use guess_the_number_core::brightness::{self, Blanking, Tick};
+use guess_the_number_core::framebuffer::{Blink, LEDMatrixFrame, Led, SevenSegmentFrame};
+use guess_the_number_core::glyph::Glyph;
+use guess_the_number_core::hal::{LEDMatrixDisplay, SevenSegmentDisplay};
+
+#[test]
+fn seven_segment_frame_keeps_digits_hiding_and_dots() {
+    let mut frame = SevenSegmentFrame::<4>::create();
+    frame.set_number(&[1, 2, 3, 4]);
+    frame.set_digit(3, None);
+    frame.hide_digit(1);
+    frame.set_dots(1 << 2);
+
+    assert_eq!(frame.glyph(0), Some(Glyph::digit(1)));
+    assert_eq!(frame.glyph(3), None);
+    assert!(frame.hidden(1));
+    assert!(!frame.hidden(0));
+    assert!(frame.dot(2));
+    assert!(!frame.dot(0));
+
+    frame.show_all_digits();
+    frame.clear();
+    assert!(!frame.hidden(1));
+    assert_eq!(frame.glyph(0), None);
+    assert!(!frame.dot(2));
+}
+
+#[test]
+fn led_matrix_frame_positions() {
+    let mut frame = LEDMatrixFrame::<4, 2>::create();
+    frame.set(1, 0);
+    frame.set(2, 1);
+
+    assert_eq!(frame.row(0), 0b0010);
+    assert_eq!(frame.row(1), 0b0100);
+    assert!(frame.get(1, 0));
+    assert!(frame.get(2, 1));
+    assert!(!frame.get(1, 1));
+
+    // outside of the matrix
+    frame.set(4, 0);
+    frame.set(0, 2);
+    assert_eq!(frame.row(0), 0b0010);
+    assert!(!frame.get(0, 2));
+}
+
+#[test]
+fn brighter_levels_are_lit_longer() {
+    let mut last = 0;
+    for level in 0..brightness::LEVELS {
+        let mut blanking = Blanking::create();
+        blanking.set_level(level);
+
+        assert_eq!(blanking.step(), Tick::Light);
+        let mut lit = 1;
+        while !blanking.slot_ended() && blanking.step() != Tick::Blank {
+            lit += 1;
+        }
+
+        assert!(lit > last);
+        last = lit;
+    }
+
+    assert_eq!(last, brightness::SLOT_TICKS);
+}
+
+#[test]
+fn frame_brightness_is_kept_in_range() {
+    let mut frame = SevenSegmentFrame::<4>::create();
+    assert_eq!(frame.brightness(), brightness::MAX_LEVEL);
+
+    frame.set_brightness(100);
+    assert_eq!(frame.brightness(), brightness::MAX_LEVEL);
+
+    // clearing the digits keeps the brightness
+    frame.set_brightness(3);
+    frame.clear();
+    assert_eq!(frame.brightness(), 3);
+}
+
+#[test]
+fn led_attributes_are_kept_until_cleared() {
+    let mut frame = LEDMatrixFrame::<4, 2>::create();
+    frame.set_led(2, 1, Led::create(3, Blink::Fast));
+    frame.set(0, 0);
+
+    assert_eq!(frame.led(2, 1), Some(Led::create(3, Blink::Fast)));
+    assert_eq!(frame.led(0, 0), Some(Led::FULL));
+    assert_eq!(frame.led(1, 0), None);
+
+    frame.clear();
+    frame.set(2, 1);
+    assert_eq!(frame.led(2, 1), Some(Led::FULL));
+}
+
+#[test]
+fn blinks_are_half_on() {
+    for blink in [Blink::Slow, Blink::Fast] {
+        let visible = (0..1000).filter(|millis| blink.visible(*millis)).count();
+        assert_eq!(visible, 500);
+    }
+    assert!((0..1000).all(|millis| Blink::Steady.visible(millis)));
+}
@@ -0,0 +1,1151 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use guess_the_number_core::animation;
+use guess_the_number_core::brightness;
+use guess_the_number_core::button::{self, Button};
+use guess_the_number_core::clock::Clock;
+use guess_the_number_core::framebuffer::{Blink, Led};
+use guess_the_number_core::game::{Game, GameState, DIGITS};
+use guess_the_number_core::glyph::Glyph;
+use guess_the_number_core::hal;
+use guess_the_number_core::rng::XorShift32;
+use guess_the_number_core::rules::GameRules;
+use guess_the_number_core::scores::HighScores;
+use guess_the_number_core::settings::{InputMode, Settings};
+use guess_the_number_core::stats::{self, Statistics};
+
+// Iterations a scripted press holds a button,
+// longer than the debounce of the buttons.
+const PRESS_ITERATIONS: u32 = 100;
+// Iterations a scripted press releases the button for,
+// so the next press is not a double click.
+const RELEASE_ITERATIONS: u32 = button::RELEASED_FOR_DOUBLE_CLICK as u32 + 100;
+// Long enough for every animation to end.
+const SETTLE_ITERATIONS: u32 = 200_000;
+// Longer than a long click.
+const LONG_PRESS_ITERATIONS: u32 = button::PRESSED_FOR_LONG as u32 + 200;
+// Roughly the speed of the main loop on the board.
+const ITERATIONS_PER_MILLISECOND: u32 = 10;
+
+fn digit(digit: u8) -> Option<Glyph> {
+    Some(Glyph::digit(digit))
+}
+
+fn letter(character: u8) -> Option<Glyph> {
+    Some(Glyph::ascii(character))
+}
+
+struct MockSevenSegment<const D: usize> {
+    digits: [Option<Glyph>; D],
+    dots: u8,
+    brightness: u8,
+}
+
+impl<const D: usize> Default for MockSevenSegment<D> {
+    fn default() -> Self {
+        MockSevenSegment {
+            digits: [None; D],
+            dots: 0,
+            brightness: 0,
+        }
+    }
+}
+
+impl<const D: usize> hal::SevenSegmentDisplay for MockSevenSegment<D> {
+    fn digits(&self) -> usize {
+        D
+    }
+
+    fn hide_digit(&mut self, _: usize) {}
+    fn show_digit(&mut self, _: usize) {}
+    fn hide_all_digits(&mut self) {}
+    fn show_all_digits(&mut self) {}
+
+    fn set_glyph(&mut self, digit_index: usize, glyph: Option<Glyph>) {
+        self.digits[digit_index] = glyph;
+    }
+
+    fn set_dots(&mut self, dots: u8) {
+        self.dots = dots;
+    }
+
+    fn set_brightness(&mut self, level: u8) {
+        self.brightness = level;
+    }
+
+    fn clear(&mut self) {
+        self.digits = [None; D];
+        self.dots = 0;
+    }
+}
+
+#[derive(Default)]
+// Wide enough for any game, a byte for each row.
+struct MockLEDMatrix {
+    data: u16,
+    blinking: u16,
+    brightness: u8,
+}
+
+impl hal::LEDMatrixDisplay for MockLEDMatrix {
+    fn width(&self) -> u8 {
+        8
+    }
+
+    fn height(&self) -> u8 {
+        2
+    }
+
+    fn get(&self, x: u8, y: u8) -> bool {
+        self.data & (1 << (y * 8 + x)) != 0
+    }
+
+    fn set_led(&mut self, x: u8, y: u8, led: Led) {
+        let bit = 1 << (y * 8 + x);
+        self.data |= bit;
+        if led.blink == Blink::Steady {
+            self.blinking &= !bit;
+        } else {
+            self.blinking |= bit;
+        }
+    }
+
+    fn set_brightness(&mut self, level: u8) {
+        self.brightness = level;
+    }
+
+    fn clear(&mut self) {
+        self.data = 0;
+        self.blinking = 0;
+    }
+}
+
+// A pull up input, low while held.
+#[derive(Clone, Default)]
+struct MockInput {
+    held: Rc<Cell<bool>>,
+}
+
+impl hal::InputPin for MockInput {
+    fn is_low(&self) -> bool {
+        self.held.get()
+    }
+}
+
+#[derive(Clone, Default)]
+struct MockClock {
+    millis: Rc<Cell<u32>>,
+}
+
+impl Clock for MockClock {
+    fn millis(&self) -> u32 {
+        self.millis.get()
+    }
+}
+
+type TestGame<const D: usize> = Game<MockSevenSegment<D>, MockLEDMatrix, MockInput, XorShift32, MockClock, D>;
+
+// Runs a game of D digits, the board has DIGITS.
+struct Harness<const D: usize> {
+    game: TestGame<D>,
+    digits: [MockInput; D],
+    confirm: MockInput,
+    clock: MockClock,
+    iterations: u32,
+}
+
+impl<const D: usize> Harness<D> {
+    fn create() -> Harness<D> {
+        let digits = [(); D].map(|_| MockInput::default());
+        let confirm = MockInput::default();
+        let clock = MockClock::default();
+
+        let game = Game::create(
+            MockSevenSegment::default(),
+            MockLEDMatrix::default(),
+            digits.clone().map(|input| Button::create(input, false)),
+            Button::create(confirm.clone(), false),
+            XorShift32::init([125, 139, 45, 0]),
+            clock.clone(),
+        );
+
+        Harness {
+            game,
+            digits,
+            confirm,
+            clock,
+            iterations: 0,
+        }
+    }
+
+    fn run(&mut self, iterations: u32) {
+        for _ in 0..iterations {
+            self.iterations += 1;
+            if self.iterations.is_multiple_of(ITERATIONS_PER_MILLISECOND) {
+                self.clock.millis.set(self.clock.millis.get().wrapping_add(1));
+            }
+
+            self.game.step_buttons();
+            self.game.step_animation();
+            self.game.step();
+        }
+    }
+
+    fn hold(&mut self, input: MockInput, iterations: u32) {
+        input.held.set(true);
+        self.run(iterations);
+        input.held.set(false);
+        self.run(RELEASE_ITERATIONS);
+    }
+
+    fn press(&mut self, input: MockInput) {
+        self.hold(input, PRESS_ITERATIONS);
+    }
+
+    fn press_confirm(&mut self) {
+        self.press(self.confirm.clone());
+    }
+
+    // A double click of confirm, it browses the guesses.
+    fn double_confirm(&mut self) {
+        self.confirm.held.set(true);
+        self.run(PRESS_ITERATIONS);
+        self.confirm.held.set(false);
+        self.run(PRESS_ITERATIONS);
+        self.press_confirm();
+    }
+
+    // Enters the number with the digit buttons, starting from the shown one.
+    fn enter(&mut self, from: [u8; D], number: [u8; D]) {
+        for (digit_index, (current, target)) in from.iter().zip(number).enumerate() {
+            let presses = (target + 10 - current) % 10;
+            for _ in 0..presses {
+                self.press(self.digits[D - 1 - digit_index].clone());
+            }
+        }
+    }
+
+    // Starts a game and wins it with the given number of attempts, at least 1.
+    fn win(&mut self, attempts: u16) {
+        self.press_confirm();
+        let secret = self.game.secret().unwrap();
+
+        for _ in 1..attempts {
+            self.press_confirm();
+        }
+        self.enter([0; D], secret);
+        self.press_confirm();
+        assert_eq!(self.game.state(), GameState::Won);
+
+        self.run(SETTLE_ITERATIONS);
+    }
+
+    fn shown(&mut self) -> [Option<Glyph>; D] {
+        self.game.seven_segment().digits
+    }
+
+    fn dots(&mut self) -> u8 {
+        self.game.seven_segment().dots
+    }
+}
+
+fn wrong_guess(secret: [u8; DIGITS]) -> [u8; DIGITS] {
+    let mut guess = secret;
+    guess[0] = (guess[0] + 1) % 10;
+    guess
+}
+
+#[test]
+fn starts_without_attempts() {
+    let harness = Harness::<DIGITS>::create();
+
+    assert_eq!(harness.game.state(), GameState::Start);
+    assert_eq!(harness.game.attempts(), 0);
+    assert_eq!(harness.game.secret(), None);
+}
+
+#[test]
+fn counts_every_confirmed_guess() {
+    let mut harness = Harness::<DIGITS>::create();
+    harness.press_confirm();
+    assert_eq!(harness.game.state(), GameState::Play);
+    let secret = harness.game.secret().unwrap();
+
+    harness.press_confirm();
+    harness.press_confirm();
+    assert_eq!(harness.game.attempts(), 2);
+
+    let guess = wrong_guess(secret);
+    harness.enter([0; DIGITS], guess);
+    harness.press_confirm();
+    assert_eq!(harness.game.attempts(), 3);
+    assert_eq!(harness.game.state(), GameState::Play);
+
+    harness.enter(guess, secret);
+    harness.press_confirm();
+    assert_eq!(harness.game.state(), GameState::Won);
+    assert_eq!(harness.game.attempts(), 4);
+}
+
+#[test]
+fn held_digit_button_counts_up_repeatedly() {
+    let mut harness = Harness::<DIGITS>::create();
+    harness.press_confirm();
+
+    // the press, the long click and two repeats
+    let iterations = u32::from(button::PRESSED_FOR_LONG + 2 * button::PRESSED_FOR_REPEAT) + PRESS_ITERATIONS;
+    harness.hold(harness.digits[DIGITS - 1].clone(), iterations);
+    assert_eq!(harness.shown()[0], digit(4));
+}
+
+#[test]
+fn double_click_counts_down() {
+    let mut harness = Harness::<DIGITS>::create();
+    harness.press_confirm();
+    let last = harness.digits[DIGITS - 1].clone();
+
+    last.held.set(true);
+    harness.run(PRESS_ITERATIONS);
+    last.held.set(false);
+    harness.run(PRESS_ITERATIONS);
+    harness.press(last.clone());
+    assert_eq!(harness.shown()[0], digit(9));
+
+    // the next double click goes on down
+    harness.press(last.clone());
+    assert_eq!(harness.shown()[0], digit(0));
+    last.held.set(true);
+    harness.run(PRESS_ITERATIONS);
+    last.held.set(false);
+    harness.run(PRESS_ITERATIONS);
+    harness.press(last);
+    assert_eq!(harness.shown()[0], digit(9));
+    assert_eq!(harness.game.attempts(), 0);
+}
+
+#[test]
+fn long_confirm_gives_up() {
+    let mut harness = Harness::<DIGITS>::create();
+    harness.press_confirm();
+    let secret = harness.game.secret().unwrap();
+    harness.press_confirm();
+    assert_eq!(harness.game.attempts(), 1);
+
+    // no guess is taken on the way
+    harness.hold(harness.confirm.clone(), LONG_PRESS_ITERATIONS);
+    assert_eq!(harness.game.state(), GameState::Start);
+    assert_eq!(harness.game.attempts(), 1);
+    assert_eq!(harness.shown(), secret.map(|digit| Some(Glyph::digit(digit))));
+    assert_eq!(harness.game.led_matrix().data, 0);
+    assert_eq!(harness.game.statistics().abandoned(), 1);
+    assert_eq!(harness.game.high_scores().best_attempts, None);
+
+    harness.press_confirm();
+    assert_eq!(harness.game.state(), GameState::Play);
+}
+
+#[test]
+fn double_confirm_browses_the_guesses() {
+    let mut harness = Harness::<DIGITS>::create();
+    harness.press_confirm();
+    let secret = harness.game.secret().unwrap();
+    let first = harness.digits[0].clone();
+    let last = harness.digits[DIGITS - 1].clone();
+
+    // nothing to browse yet, nor is it a guess
+    harness.double_confirm();
+    assert_eq!(harness.game.state(), GameState::Play);
+    assert_eq!(harness.game.attempts(), 0);
+
+    let older = wrong_guess(secret);
+    harness.enter([0; DIGITS], older);
+    harness.press_confirm();
+    harness.run(SETTLE_ITERATIONS);
+    let older_marks = harness.game.led_matrix().data;
+
+    let mut newer = older;
+    newer[DIGITS - 1] = (newer[DIGITS - 1] + 5) % 10;
+    harness.enter(older, newer);
+    harness.press_confirm();
+    harness.run(SETTLE_ITERATIONS);
+    let newer_marks = harness.game.led_matrix().data;
+    assert_ne!(older_marks, newer_marks);
+    harness.press(last.clone());
+    let entry = harness.shown();
+
+    harness.double_confirm();
+    assert_eq!(harness.game.state(), GameState::History);
+    assert_eq!(harness.shown(), newer.map(|digit| Some(Glyph::digit(digit))));
+    assert_eq!(harness.dots(), 0b1111);
+
+    // the first button goes back, not past the oldest guess
+    harness.press(first.clone());
+    harness.press(first);
+    assert_eq!(harness.shown(), older.map(|digit| Some(Glyph::digit(digit))));
+    assert_eq!(harness.game.led_matrix().data, older_marks);
+
+    // the last one forward, past the newest back to the entry
+    harness.press(last.clone());
+    assert_eq!(harness.game.led_matrix().data, newer_marks);
+    harness.press(last);
+    assert_eq!(harness.game.state(), GameState::Play);
+    assert_eq!(harness.shown(), entry);
+    assert_eq!(harness.dots(), 0);
+
+    // confirm goes back as well
+    harness.double_confirm();
+    harness.press_confirm();
+    assert_eq!(harness.game.state(), GameState::Play);
+    assert_eq!(harness.shown(), entry);
+    assert_eq!(harness.game.led_matrix().data, newer_marks);
+    assert_eq!(harness.game.attempts(), 2);
+}
+
+#[test]
+fn eight_digit_history_lights_every_dot() {
+    let mut harness = Harness::<8>::create();
+    harness.press_confirm();
+
+    // the secret does not start with 0
+    harness.press_confirm();
+    harness.double_confirm();
+    assert_eq!(harness.game.state(), GameState::History);
+    assert_eq!(harness.shown(), [digit(0); 8]);
+    assert_eq!(harness.dots(), 0xFF);
+}
+
+#[test]
+fn game_is_lost_out_of_attempts() {
+    let mut harness = Harness::<DIGITS>::create();
+    harness.game.set_settings(Settings { max_attempts: 2, ..Settings::default() });
+    harness.press_confirm();
+    let secret = harness.game.secret().unwrap();
+
+    harness.press_confirm();
+    assert_eq!(harness.game.state(), GameState::Play);
+    harness.press_confirm();
+    assert_eq!(harness.game.state(), GameState::Lost);
+    assert_eq!(harness.game.secret(), None);
+    assert!(harness.game.take_save_request());
+
+    // revealed from the left
+    assert_eq!(harness.shown(), [letter(b'-'); DIGITS]);
+    harness.run(12 * 255);
+    assert_eq!(harness.shown()[DIGITS - 1], digit(secret[DIGITS - 1]));
+    assert_eq!(harness.shown()[DIGITS - 2], letter(b'-'));
+
+    harness.run(SETTLE_ITERATIONS);
+    assert_eq!(harness.shown(), secret.map(|digit| Some(Glyph::digit(digit))));
+    assert_eq!(harness.game.led_matrix().data, 0);
+
+    let statistics = harness.game.statistics();
+    assert_eq!(statistics.lost, 1);
+    assert_eq!(statistics.abandoned(), 0);
+    assert_eq!(harness.game.high_scores().best_attempts, None);
+
+    harness.press_confirm();
+    assert_eq!(harness.game.state(), GameState::Play);
+}
+
+#[test]
+fn settings_limit_the_attempts() {
+    let mut harness = Harness::<DIGITS>::create();
+    harness.hold(harness.confirm.clone(), LONG_PRESS_ITERATIONS);
+    harness.hold(harness.confirm.clone(), LONG_PRESS_ITERATIONS);
+
+    // no limit by default
+    for _ in 0..3 {
+        harness.press(harness.digits[1].clone());
+    }
+    assert_eq!(harness.shown(), [letter(b'-'), letter(b'-'), letter(b'-'), letter(b't')]);
+
+    let down = harness.digits[DIGITS - 2].clone();
+    let up = harness.digits[DIGITS - 1].clone();
+    for _ in 0..12 {
+        harness.press(up.clone());
+    }
+    harness.press(down);
+    assert_eq!(harness.shown(), [digit(1), digit(1), None, letter(b't')]);
+    assert_eq!(harness.game.settings().max_attempts, 11);
+
+    harness.press_confirm();
+    assert!(harness.game.take_save_request());
+}
+
+#[test]
+fn digit_button_on_start_picks_the_mode() {
+    let mut harness = Harness::<DIGITS>::create();
+    let first = harness.digits[0].clone();
+    let last = harness.digits[DIGITS - 1].clone();
+
+    harness.press(last.clone());
+    assert_eq!(harness.game.state(), GameState::ModeSelect);
+    assert_eq!(harness.shown(), [letter(b'-'), letter(b'-'), letter(b'-'), letter(b'c')]);
+    harness.press(last);
+    assert_eq!(harness.shown(), [digit(0), digit(6), None, letter(b'c')]);
+
+    // back over the classic mode to the longest countdown
+    harness.press(first.clone());
+    harness.press(first);
+    assert_eq!(harness.shown(), [digit(0), digit(0), digit(3), letter(b'c')]);
+
+    harness.press_confirm();
+    assert_eq!(harness.game.state(), GameState::Play);
+    assert_eq!(harness.game.statistics().played, 1);
+}
+
+#[test]
+fn time_attack_is_lost_when_the_time_is_up() {
+    let mut harness = Harness::<DIGITS>::create();
+    harness.press(harness.digits[DIGITS - 1].clone());
+    harness.press(harness.digits[DIGITS - 1].clone());
+    harness.confirm.held.set(true);
+    harness.run(PRESS_ITERATIONS);
+    harness.confirm.held.set(false);
+    harness.run(PRESS_ITERATIONS);
+    let started = harness.clock.millis.get();
+    let secret = harness.game.secret().unwrap();
+
+    // the time left flashes on the start and after the guesses
+    assert_eq!(harness.shown(), [digit(0), digit(0), digit(1), digit(0)]);
+    assert_eq!(harness.dots(), animation::TIME_DOTS);
+    harness.run(PRESS_ITERATIONS);
+    assert_eq!(harness.dots(), animation::TIME_DOTS);
+    // for a second of the clock, however many steps it takes
+    harness.clock.millis.set(started + 1_000);
+    harness.run(1);
+    assert_eq!(harness.shown(), [digit(0); DIGITS]);
+    assert_eq!(harness.dots(), 0);
+
+    harness.clock.millis.set(started + 15_000);
+    harness.press_confirm();
+    assert_eq!(harness.shown(), [digit(4), digit(4), digit(0), digit(0)]);
+    assert_eq!(harness.dots(), animation::TIME_DOTS);
+
+    // a button brings the entry back at once
+    harness.press(harness.digits[DIGITS - 1].clone());
+    assert_eq!(harness.shown(), [digit(1), digit(0), digit(0), digit(0)]);
+
+    harness.clock.millis.set(started + 60_000);
+    harness.run(1);
+    assert_eq!(harness.game.state(), GameState::Lost);
+    assert_eq!(harness.game.statistics().lost, 1);
+
+    harness.run(SETTLE_ITERATIONS);
+    assert_eq!(harness.shown(), secret.map(|digit| Some(Glyph::digit(digit))));
+}
+
+#[test]
+fn time_running_out_while_a_button_is_held_shows_the_secret() {
+    let mut harness = Harness::<DIGITS>::create();
+    harness.game.set_settings(Settings { input_mode: InputMode::Shift, ..Settings::default() });
+    harness.press(harness.digits[DIGITS - 1].clone());
+    harness.press(harness.digits[DIGITS - 1].clone());
+    harness.press_confirm();
+    let started = harness.clock.millis.get();
+    let secret = harness.game.secret().unwrap();
+
+    // confirm held as the shift when the time is up
+    harness.confirm.held.set(true);
+    harness.run(PRESS_ITERATIONS);
+    harness.clock.millis.set(started + 60_000);
+    harness.run(1);
+    assert_eq!(harness.game.state(), GameState::Lost);
+
+    // the release does not start the next game
+    harness.confirm.held.set(false);
+    harness.run(RELEASE_ITERATIONS);
+    assert_eq!(harness.game.state(), GameState::Lost);
+    harness.run(SETTLE_ITERATIONS);
+    assert_eq!(harness.shown(), secret.map(|digit| Some(Glyph::digit(digit))));
+}
+
+fn with_input_mode(input_mode: InputMode) -> Harness<DIGITS> {
+    let mut harness = Harness::<DIGITS>::create();
+    harness.game.set_settings(Settings { input_mode, ..Settings::default() });
+    harness.press_confirm();
+    harness
+}
+
+#[test]
+fn shift_counts_down_without_a_guess() {
+    let mut harness = with_input_mode(InputMode::Shift);
+    let last = harness.digits[DIGITS - 1].clone();
+
+    harness.confirm.held.set(true);
+    harness.run(PRESS_ITERATIONS);
+    harness.press(last.clone());
+    harness.press(last.clone());
+    assert_eq!(harness.shown()[0], digit(8));
+
+    // nor does it give up, held long
+    harness.hold(harness.confirm.clone(), LONG_PRESS_ITERATIONS);
+    assert_eq!(harness.game.state(), GameState::Play);
+    assert_eq!(harness.game.attempts(), 0);
+
+    // without the shift the buttons count up
+    harness.press(last);
+    assert_eq!(harness.shown()[0], digit(9));
+    harness.press_confirm();
+    assert_eq!(harness.game.attempts(), 1);
+}
+
+#[test]
+fn cursor_picks_the_digit_to_change() {
+    let mut harness = with_input_mode(InputMode::Cursor);
+    let left = harness.digits[0].clone();
+    let right = harness.digits[1].clone();
+    let down = harness.digits[DIGITS - 2].clone();
+    let up = harness.digits[DIGITS - 1].clone();
+
+    // starts on the leftmost digit
+    assert_eq!(harness.dots(), 1 << (DIGITS - 1));
+    harness.press(up.clone());
+    assert_eq!(harness.shown(), [digit(0), digit(0), digit(0), digit(1)]);
+    harness.press(down.clone());
+    harness.press(down.clone());
+    assert_eq!(harness.shown(), [digit(0), digit(0), digit(0), digit(9)]);
+
+    harness.press(right.clone());
+    assert_eq!(harness.dots(), 1 << (DIGITS - 2));
+    harness.press(up.clone());
+    assert_eq!(harness.shown(), [digit(0), digit(0), digit(1), digit(9)]);
+
+    // wraps around
+    harness.press(left.clone());
+    harness.press(left);
+    assert_eq!(harness.dots(), 1);
+    harness.press(up);
+    assert_eq!(harness.shown(), [digit(1), digit(0), digit(1), digit(9)]);
+}
+
+#[test]
+fn win_animation_ends_on_attempts() {
+    let mut harness = Harness::<DIGITS>::create();
+    harness.press_confirm();
+    let secret = harness.game.secret().unwrap();
+
+    harness.press_confirm();
+    harness.enter([0; DIGITS], secret);
+    harness.press_confirm();
+    harness.run(SETTLE_ITERATIONS);
+
+    assert_eq!(harness.shown(), animation::attempts_digits(2));
+}
+
+#[test]
+fn new_game_resets_attempts() {
+    let mut harness = Harness::<DIGITS>::create();
+    harness.press_confirm();
+    let secret = harness.game.secret().unwrap();
+    harness.enter([0; DIGITS], secret);
+    harness.press_confirm();
+    assert_eq!(harness.game.attempts(), 1);
+
+    harness.run(SETTLE_ITERATIONS);
+    harness.press_confirm();
+    assert_eq!(harness.game.state(), GameState::Play);
+    assert_eq!(harness.game.attempts(), 0);
+}
+
+#[test]
+fn attempts_are_right_aligned_after_label() {
+    let t = letter(b't');
+
+    assert_eq!(animation::attempts_digits(7), [digit(7), None, None, t]);
+    assert_eq!(animation::attempts_digits(42), [digit(2), digit(4), None, t]);
+    assert_eq!(animation::attempts_digits(305), [digit(5), digit(0), digit(3), t]);
+    assert_eq!(animation::attempts_digits(5000), [digit(9), digit(9), digit(9), t]);
+}
+
+#[test]
+fn win_sets_best_attempts() {
+    let mut harness = Harness::<DIGITS>::create();
+    assert_eq!(harness.game.high_scores().best_attempts, None);
+
+    harness.win(3);
+    assert_eq!(harness.game.high_scores().best_attempts, Some(3));
+    assert!(harness.game.take_save_request());
+    assert!(!harness.game.take_save_request());
+}
+
+#[test]
+fn worse_game_keeps_best_attempts() {
+    let mut harness = Harness::<DIGITS>::create();
+    harness.win(2);
+    harness.game.take_save_request();
+
+    harness.win(5);
+    assert_eq!(harness.game.high_scores().best_attempts, Some(2));
+
+    harness.win(1);
+    assert_eq!(harness.game.high_scores().best_attempts, Some(1));
+    assert!(harness.game.take_save_request());
+}
+
+#[test]
+fn long_confirm_on_start_shows_high_scores() {
+    let mut harness = Harness::<DIGITS>::create();
+    harness.game.set_high_scores(HighScores {
+        best_attempts: Some(12),
+        best_time: None,
+    });
+
+    harness.hold(harness.confirm.clone(), LONG_PRESS_ITERATIONS);
+    assert_eq!(harness.game.state(), GameState::Stats);
+    assert_eq!(harness.shown(), animation::best_attempts_digits(Some(12)));
+    assert_eq!(harness.shown(), [digit(2), digit(1), None, letter(b'b')]);
+}
+
+#[test]
+fn high_scores_without_won_game_are_dashes() {
+    let mut harness = Harness::<DIGITS>::create();
+
+    harness.hold(harness.confirm.clone(), LONG_PRESS_ITERATIONS);
+    assert_eq!(harness.shown(), [letter(b'-'), letter(b'-'), letter(b'-'), letter(b'b')]);
+}
+
+#[test]
+fn reset_combination_is_first_and_last_button() {
+    let mut harness = Harness::<DIGITS>::create();
+    assert!(!harness.game.reset_combination_held());
+
+    harness.digits[0].held.set(true);
+    assert!(!harness.game.reset_combination_held());
+
+    harness.digits[DIGITS - 1].held.set(true);
+    assert!(harness.game.reset_combination_held());
+
+    // holding them into the game loop does not start a game
+    harness.run(PRESS_ITERATIONS);
+    assert_eq!(harness.game.state(), GameState::Start);
+}
+
+#[test]
+fn reset_clears_high_scores() {
+    let mut harness = Harness::<DIGITS>::create();
+    harness.win(4);
+    harness.game.take_save_request();
+
+    harness.game.reset_high_scores();
+    assert_eq!(*harness.game.high_scores(), HighScores::default());
+    assert!(harness.game.take_save_request());
+}
+
+#[test]
+fn games_are_counted() {
+    let mut harness = Harness::<DIGITS>::create();
+    harness.win(2);
+    harness.win(2);
+    harness.win(12);
+
+    // started and left unfinished
+    harness.press_confirm();
+    assert!(harness.game.take_save_request());
+
+    let statistics = harness.game.statistics();
+    assert_eq!(statistics.played, 4);
+    assert_eq!(statistics.won, 3);
+    assert_eq!(statistics.abandoned(), 1);
+    assert_eq!(statistics.histogram[1], 2);
+    assert_eq!(statistics.histogram[stats::HISTOGRAM_BUCKETS - 1], 1);
+}
+
+#[test]
+fn digit_buttons_page_through_stats() {
+    let mut harness = Harness::<DIGITS>::create();
+    let mut statistics = Statistics {
+        played: 1234,
+        won: 40,
+        ..Statistics::default()
+    };
+    statistics.histogram[2] = 7;
+    harness.game.set_statistics(statistics);
+
+    harness.hold(harness.confirm.clone(), LONG_PRESS_ITERATIONS);
+    let last = harness.digits[DIGITS - 1].clone();
+    let first = harness.digits[0].clone();
+
+    harness.press(last.clone());
+    assert_eq!(harness.shown(), [letter(b'-'); 4]);
+    harness.press(last.clone());
+    assert_eq!(harness.shown(), [digit(4), digit(3), digit(2), digit(1)]);
+    harness.press(last.clone());
+    assert_eq!(harness.shown(), [digit(0), digit(4), None, letter(b'G')]);
+    harness.press(last.clone());
+    assert_eq!(harness.shown(), [digit(4), digit(9), digit(1), digit(1)]);
+    harness.press(first.clone());
+    harness.press(first.clone());
+    harness.press(first.clone());
+    harness.press(first.clone());
+    assert_eq!(harness.shown(), animation::best_attempts_digits(None));
+
+    // back over the first page to the histogram
+    harness.press(first.clone());
+    assert_eq!(harness.shown(), [digit(0), None, None, digit(9)]);
+}
+
+#[test]
+fn confirm_leaves_stats() {
+    let mut harness = Harness::<DIGITS>::create();
+    harness.hold(harness.confirm.clone(), LONG_PRESS_ITERATIONS);
+
+    harness.press_confirm();
+    assert_eq!(harness.game.state(), GameState::Start);
+    assert_eq!(harness.game.statistics().played, 0);
+}
+
+#[test]
+fn stopwatch_runs_during_the_game() {
+    let mut harness = Harness::<DIGITS>::create();
+    assert_eq!(harness.game.elapsed(), 0);
+
+    harness.press_confirm();
+    let secret = harness.game.secret().unwrap();
+    // the game starts on the release of confirm
+    let started = harness.clock.millis();
+
+    harness.enter([0; DIGITS], secret);
+    harness.clock.millis.set(started + 83_000);
+    harness.press_confirm();
+    assert_eq!(harness.game.state(), GameState::Won);
+
+    let time = harness.game.elapsed();
+    assert!(time > 83_000 && time < 84_000, "{}", time);
+
+    // stopped after the win
+    harness.run(SETTLE_ITERATIONS);
+    assert_eq!(harness.game.elapsed(), time);
+    assert_eq!(harness.game.high_scores().best_time, Some(time));
+}
+
+#[test]
+fn win_animation_shows_time_with_dot() {
+    let mut harness = Harness::<DIGITS>::create();
+    harness.press_confirm();
+    let secret = harness.game.secret().unwrap();
+    let started = harness.clock.millis();
+    harness.enter([0; DIGITS], secret);
+    harness.clock.millis.set(started + 83_000);
+    harness.press_confirm();
+
+    let mut shown_time = false;
+    for _ in 0..SETTLE_ITERATIONS / 100 {
+        harness.run(100);
+        if harness.dots() == animation::TIME_DOTS {
+            // 1 minute 23 seconds
+            assert_eq!(harness.shown(), [digit(3), digit(2), digit(1), digit(0)]);
+            shown_time = true;
+        }
+    }
+
+    assert!(shown_time);
+    assert_eq!(harness.dots(), 0);
+}
+
+#[test]
+fn best_time_keeps_the_shortest_game() {
+    let mut harness = Harness::<DIGITS>::create();
+    harness.game.set_high_scores(HighScores {
+        best_attempts: None,
+        best_time: Some(100),
+    });
+
+    harness.win(1);
+    assert!(harness.game.elapsed() > 100);
+    assert_eq!(harness.game.high_scores().best_time, Some(100));
+}
+
+#[test]
+fn time_is_minutes_and_seconds() {
+    assert_eq!(animation::time_digits(Some(0)), [digit(0); 4]);
+    assert_eq!(animation::time_digits(Some(59_999)), [digit(9), digit(5), digit(0), digit(0)]);
+    assert_eq!(animation::time_digits(Some(61_000)), [digit(1), digit(0), digit(1), digit(0)]);
+    assert_eq!(animation::time_digits(Some(100 * 60_000)), [digit(9), digit(5), digit(9), digit(9)]);
+    assert_eq!(animation::time_digits(None), [letter(b'-'); 4]);
+
+    // M.SS on three digits, the minutes after the seconds on more
+    assert_eq!(animation::time_digits(Some(61_000)), [digit(1), digit(0), digit(1)]);
+    assert_eq!(animation::time_digits(Some(100 * 60_000)), [digit(9), digit(5), digit(9)]);
+    assert_eq!(
+        animation::time_digits(Some(61_000)),
+        [digit(1), digit(0), digit(1), digit(0), None, None]
+    );
+}
+
+#[test]
+fn labels_fit_the_digits() {
+    assert_eq!(animation::labeled_digits(Glyph::ascii(b'P'), Some(99)), [digit(9), digit(9), letter(b'P')]);
+    assert_eq!(animation::labeled_digits(Glyph::ascii(b'P'), Some(100)), [digit(0), digit(0), digit(1)]);
+    assert_eq!(animation::labeled_digits(Glyph::ascii(b'P'), Some(5000)), [digit(9), digit(9), digit(9)]);
+    assert_eq!(
+        animation::labeled_digits(Glyph::ascii(b'P'), Some(12345)),
+        [digit(5), digit(4), digit(3), digit(2), digit(1), letter(b'P')]
+    );
+    assert_eq!(animation::attempts_digits(1500), [digit(9), digit(9), letter(b't')]);
+}
+
+#[test]
+fn rules_change_from_the_next_game() {
+    let mut harness = Harness::<DIGITS>::create();
+    harness.press_confirm();
+    let secret = harness.game.secret().unwrap();
+
+    let rules = GameRules { length: 2, alphabet: 6, ..GameRules::default() };
+    assert!(harness.game.set_rules(rules));
+    assert!(!harness.game.set_rules(GameRules { length: 5, ..GameRules::default() }));
+    assert_eq!(harness.game.rules().length(), DIGITS);
+
+    // the running game is still won by its secret
+    harness.enter([0; DIGITS], secret);
+    harness.press_confirm();
+    assert_eq!(harness.game.state(), GameState::Won);
+    harness.run(SETTLE_ITERATIONS);
+
+    harness.press_confirm();
+    assert_eq!(*harness.game.rules(), rules);
+    let secret = harness.game.secret().unwrap();
+    assert!(secret[..2].iter().all(|digit| *digit < 6));
+    assert_eq!(secret[2..], [0, 0]);
+    assert_eq!(harness.shown(), [digit(0), digit(0), None, None]);
+
+    harness.enter([0; DIGITS], secret);
+    harness.press_confirm();
+    assert_eq!(harness.game.state(), GameState::Won);
+}
+
+#[test]
+fn six_digit_game_is_won() {
+    let mut harness = Harness::<6>::create();
+    assert_eq!(harness.game.rules().length(), 6);
+
+    harness.win(3);
+    assert_eq!(harness.game.attempts(), 3);
+    assert_eq!(harness.shown(), [digit(3), None, None, None, None, letter(b't')]);
+    assert_eq!(harness.game.high_scores().best_attempts, Some(3));
+}
+
+#[test]
+fn six_digit_game_marks_every_digit() {
+    let mut harness = Harness::<6>::create();
+    harness.press_confirm();
+    let secret = harness.game.secret().unwrap();
+
+    let mut guess = secret;
+    guess[5] = (guess[5] + 1) % 10;
+    if secret.contains(&guess[5]) {
+        guess[5] = (guess[5] + 1) % 10;
+    }
+    harness.enter([0; 6], guess);
+    harness.press_confirm();
+    harness.run(SETTLE_ITERATIONS);
+
+    let red = harness.game.led_matrix().data & 0xFF;
+    assert_eq!(red & 0b1_1111, 0b1_1111);
+    assert_eq!(red & 0b10_0000, 0);
+}
+
+#[test]
+fn three_digit_game_is_won() {
+    let mut harness = Harness::<3>::create();
+    harness.win(2);
+    assert_eq!(harness.shown(), [digit(2), None, letter(b't')]);
+
+    // the settings fit three digits, the first button picks the setting
+    harness.hold(harness.confirm.clone(), LONG_PRESS_ITERATIONS);
+    harness.hold(harness.confirm.clone(), LONG_PRESS_ITERATIONS);
+    assert_eq!(harness.game.state(), GameState::Settings);
+    assert_eq!(harness.shown(), [digit(8), None, letter(b'd')]);
+    harness.press(harness.digits[0].clone());
+    assert_eq!(harness.shown(), [digit(0), None, letter(b'o')]);
+    harness.press(harness.digits[0].clone());
+    assert_eq!(harness.shown(), [digit(0), None, letter(b'U')]);
+    harness.press(harness.digits[0].clone());
+    assert_eq!(harness.shown(), [digit(0), digit(1), letter(b'A')]);
+
+    // no longer than the digits
+    harness.press(harness.digits[0].clone());
+    harness.press(harness.digits[2].clone());
+    assert_eq!(harness.shown(), [digit(3), None, letter(b'n')]);
+
+    harness.press(harness.digits[0].clone());
+    assert_eq!(harness.shown(), [letter(b'-'), letter(b'-'), letter(b't')]);
+    harness.press(harness.digits[0].clone());
+    assert_eq!(harness.shown(), [digit(1), None, letter(b'E')]);
+    harness.press(harness.digits[0].clone());
+    assert_eq!(harness.shown(), [digit(8), None, letter(b'L')]);
+    harness.press(harness.digits[1].clone());
+    assert_eq!(harness.shown(), [digit(7), None, letter(b'L')]);
+}
+
+#[test]
+fn settings_pick_the_rules_of_the_next_game() {
+    let mut harness = Harness::<DIGITS>::create();
+    harness.hold(harness.confirm.clone(), LONG_PRESS_ITERATIONS);
+    harness.hold(harness.confirm.clone(), LONG_PRESS_ITERATIONS);
+    let back = harness.digits[0].clone();
+    let down = harness.digits[DIGITS - 2].clone();
+    let up = harness.digits[DIGITS - 1].clone();
+
+    // leading zero, unique digits, alphabet, length from the back
+    harness.press(back.clone());
+    harness.press(up.clone());
+    assert_eq!(harness.shown(), [digit(1), None, None, letter(b'o')]);
+    harness.press(back.clone());
+    harness.press(up.clone());
+    assert_eq!(harness.shown(), [digit(1), None, None, letter(b'U')]);
+
+    // unique digits need as many digits in the alphabet as the length
+    harness.press(back.clone());
+    for _ in 0..7 {
+        harness.press(down.clone());
+    }
+    assert_eq!(harness.shown(), [digit(4), None, None, letter(b'A')]);
+    // and no longer than the digits
+    harness.press(back);
+    harness.press(down);
+    assert_eq!(harness.shown(), [digit(3), None, None, letter(b'n')]);
+    harness.press(up.clone());
+    harness.press(up);
+    assert_eq!(harness.shown(), [digit(4), None, None, letter(b'n')]);
+
+    let rules = GameRules { unique_digits: true, leading_zero: true, alphabet: 4, length: 4 };
+    assert_eq!(harness.game.settings().rules, rules);
+    harness.press_confirm();
+    assert!(harness.game.take_save_request());
+
+    harness.press_confirm();
+    assert_eq!(*harness.game.rules(), rules);
+    let mut secret = harness.game.secret().unwrap();
+    secret.sort();
+    assert_eq!(secret, [0, 1, 2, 3]);
+}
+
+#[test]
+fn long_confirm_on_stats_opens_settings() {
+    let mut harness = Harness::<DIGITS>::create();
+    harness.hold(harness.confirm.clone(), LONG_PRESS_ITERATIONS);
+    harness.hold(harness.confirm.clone(), LONG_PRESS_ITERATIONS);
+
+    assert_eq!(harness.game.state(), GameState::Settings);
+    assert_eq!(harness.shown(), [digit(8), None, None, letter(b'd')]);
+}
+
+#[test]
+fn settings_change_brightness() {
+    let mut harness = Harness::<DIGITS>::create();
+    harness.game.set_settings(Settings::default());
+    harness.hold(harness.confirm.clone(), LONG_PRESS_ITERATIONS);
+    harness.hold(harness.confirm.clone(), LONG_PRESS_ITERATIONS);
+    harness.game.take_save_request();
+
+    let down = harness.digits[DIGITS - 2].clone();
+    let up = harness.digits[DIGITS - 1].clone();
+    let next = harness.digits[1].clone();
+
+    // stops at the brightest level
+    harness.press(up.clone());
+    harness.press(down.clone());
+    harness.press(down.clone());
+    assert_eq!(harness.shown(), [digit(6), None, None, letter(b'd')]);
+    assert_eq!(harness.game.seven_segment().brightness, brightness::MAX_LEVEL - 2);
+
+    harness.press(next);
+    assert_eq!(harness.shown(), [digit(8), None, None, letter(b'L')]);
+    for _ in 0..brightness::LEVELS {
+        harness.press(down.clone());
+    }
+    assert_eq!(harness.shown(), [digit(1), None, None, letter(b'L')]);
+    assert_eq!(harness.game.led_matrix().brightness, 0);
+    assert!(!harness.game.take_save_request());
+
+    // saved once when the menu is left
+    harness.press_confirm();
+    assert_eq!(harness.game.state(), GameState::Start);
+    assert!(harness.game.take_save_request());
+    assert_eq!(
+        *harness.game.settings(),
+        Settings {
+            seven_segment_brightness: brightness::MAX_LEVEL - 2,
+            led_matrix_brightness: 0,
+            ..Settings::default()
+        }
+    );
+}
+
+#[test]
+fn settings_pick_the_input_mode() {
+    let mut harness = Harness::<DIGITS>::create();
+    harness.hold(harness.confirm.clone(), LONG_PRESS_ITERATIONS);
+    harness.hold(harness.confirm.clone(), LONG_PRESS_ITERATIONS);
+
+    harness.press(harness.digits[1].clone());
+    harness.press(harness.digits[1].clone());
+    assert_eq!(harness.shown(), [digit(1), None, None, letter(b'E')]);
+    for _ in 0..3 {
+        harness.press(harness.digits[DIGITS - 1].clone());
+    }
+    assert_eq!(harness.shown(), [digit(3), None, None, letter(b'E')]);
+    assert_eq!(harness.game.settings().input_mode, InputMode::Cursor);
+}
+
+#[test]
+fn unchanged_settings_are_not_saved() {
+    let mut harness = Harness::<DIGITS>::create();
+    harness.hold(harness.confirm.clone(), LONG_PRESS_ITERATIONS);
+    harness.hold(harness.confirm.clone(), LONG_PRESS_ITERATIONS);
+    harness.game.take_save_request();
+
+    harness.press(harness.digits[0].clone());
+    harness.press_confirm();
+    assert_eq!(harness.game.state(), GameState::Start);
+    assert!(!harness.game.take_save_request());
+}
+
+#[test]
+fn misplaced_digits_blink() {
+    let mut harness = Harness::<DIGITS>::create();
+    harness.press_confirm();
+    let secret = harness.game.secret().unwrap();
+    assert_ne!(secret[0], secret[1]);
+
+    let mut guess = secret;
+    guess.swap(0, 1);
+    harness.enter([0; DIGITS], guess);
+    harness.press_confirm();
+    harness.run(SETTLE_ITERATIONS);
+
+    // the first two digits in the yellow row, the others in the red one
+    let led_matrix = harness.game.led_matrix();
+    assert_eq!(led_matrix.data, 0b0000_0011_0000_1100);
+    assert_eq!(led_matrix.blinking, 0b0000_0011_0000_0000);
+}
+
+#[test]
+fn marquee_scrolls_the_text_through() {
+    let mut harness = Harness::<DIGITS>::create();
+    harness.run(SETTLE_ITERATIONS);
+
+    harness.game.show_text(&"Hi. M", 10);
+    harness.run(1);
+    assert_eq!(harness.shown(), [letter(b'H'), None, None, None]);
+    harness.run(10);
+    assert_eq!(harness.shown(), [letter(b'i'), letter(b'H'), None, None]);
+    harness.run(30);
+    assert_eq!(harness.shown(), [Some(Glyph::FALLBACK), letter(b' '), letter(b'.'), letter(b'i')]);
+
+    // out on the left, then blank
+    harness.run(30);
+    assert_eq!(harness.shown(), [None, None, None, Some(Glyph::FALLBACK)]);
+    harness.run(10);
+    assert_eq!(harness.shown(), [None; DIGITS]);
+
+    // a new game stops it
+    harness.game.show_text(&"PLAY", 10);
+    harness.run(1);
+    harness.press_confirm();
+    assert_eq!(harness.game.state(), GameState::Play);
+    harness.run(100);
+    assert!(harness.shown().iter().all(|glyph| *glyph != letter(b'P')));
+}
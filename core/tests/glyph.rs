@@ -0,0 +1,84 @@
+use guess_the_number_core::framebuffer::SevenSegmentFrame;
+use guess_the_number_core::glyph::Glyph;
+use guess_the_number_core::hal::SevenSegmentDisplay;
+
+fn shown<const D: usize>(frame: &SevenSegmentFrame<D>) -> Vec<Option<Glyph>> {
+    (0..D).rev().map(|digit_index| frame.glyph(digit_index)).collect()
+}
+
+fn dots<const D: usize>(frame: &SevenSegmentFrame<D>) -> Vec<bool> {
+    (0..D).rev().map(|digit_index| frame.dot(digit_index)).collect()
+}
+
+#[test]
+fn numbers_are_their_characters() {
+    for digit in 0..10 {
+        assert_eq!(Glyph::digit(digit), Glyph::ascii(b'0' + digit));
+    }
+    assert_eq!(Glyph::digit(10), Glyph::FALLBACK);
+}
+
+#[test]
+fn font_covers_printable_ascii() {
+    assert_eq!(Glyph::try_ascii(b' '), Some(Glyph::BLANK));
+    assert_eq!(Glyph::try_ascii(b'-'), Some(Glyph::DASH));
+
+    // every letter is shown, apart from the ones needing diagonals
+    for letter in (b'A'..=b'Z').chain(b'a'..=b'z') {
+        let shown = Glyph::try_ascii(letter).is_some();
+        assert_eq!(shown, !b"KMVWXkmvwx".contains(&letter), "{}", letter as char);
+    }
+
+    // nothing outside of the printable characters
+    assert_eq!(Glyph::try_ascii(b'\n'), None);
+    assert_eq!(Glyph::try_ascii(127), None);
+    assert_eq!(Glyph::from_char('é'), None);
+
+    // no glyph lights the decimal point, apart from the dot
+    for character in b' '..=b'~' {
+        if let Some(glyph) = Glyph::try_ascii(character) {
+            assert_eq!(glyph.segments() & 0x80 != 0, character == b'.');
+        }
+    }
+}
+
+#[test]
+fn constants_take_the_same_font() {
+    for character in 0..=u8::MAX {
+        assert_eq!(Glyph::const_ascii(character), Glyph::ascii(character), "{}", character);
+    }
+}
+
+#[test]
+fn strings_are_shown_from_the_left() {
+    let mut frame = SevenSegmentFrame::<4>::create();
+    frame.display_str("PLAY");
+    let letters = b"PLAY".iter().map(|letter| Some(Glyph::ascii(*letter))).collect::<Vec<_>>();
+    assert_eq!(shown(&frame), letters);
+
+    // the rest is blank, longer strings are cut
+    frame.display_str("Hi");
+    assert_eq!(shown(&frame), [Some(Glyph::ascii(b'H')), Some(Glyph::ascii(b'i')), None, None]);
+    frame.display_str("HELLO");
+    assert_eq!(shown(&frame)[3], Some(Glyph::ascii(b'L')));
+}
+
+#[test]
+fn missing_characters_fall_back() {
+    let mut frame = SevenSegmentFrame::<3>::create();
+    frame.display_str("Mé1");
+    assert_eq!(shown(&frame), [Some(Glyph::FALLBACK), Some(Glyph::FALLBACK), Some(Glyph::digit(1))]);
+}
+
+#[test]
+fn dots_join_the_character_before() {
+    let mut frame = SevenSegmentFrame::<4>::create();
+    frame.display_str("12.34");
+    assert_eq!(shown(&frame), [1, 2, 3, 4].map(|digit| Some(Glyph::digit(digit))));
+    assert_eq!(dots(&frame), [false, true, false, false]);
+
+    // a dot on its own takes a digit
+    frame.display_str(".5..");
+    assert_eq!(shown(&frame), [None, Some(Glyph::digit(5)), None, None]);
+    assert_eq!(dots(&frame), [true, true, true, false]);
+}
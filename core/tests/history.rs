@@ -0,0 +1,40 @@
+use guess_the_number_core::history::{Guess, History, SIZE};
+use guess_the_number_core::scoring::Mark;
+
+fn guess(digit: u8) -> Guess<4> {
+    Guess {
+        number: [digit; 4],
+        marks: [Mark::Correct, Mark::Misplaced, Mark::Absent, Mark::Absent],
+    }
+}
+
+#[test]
+fn newest_guess_comes_first() {
+    let mut history = History::<4>::create();
+    assert!(history.is_empty());
+    assert_eq!(history.get(0), None);
+
+    history.push(guess(1));
+    history.push(guess(2));
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(0), Some(&guess(2)));
+    assert_eq!(history.get(1), Some(&guess(1)));
+    assert_eq!(history.get(2), None);
+}
+
+#[test]
+fn oldest_guesses_are_dropped() {
+    let mut history = History::<4>::create();
+    for digit in 0..SIZE as u8 + 3 {
+        history.push(guess(digit));
+    }
+
+    assert_eq!(history.len(), SIZE);
+    assert_eq!(history.get(0), Some(&guess(SIZE as u8 + 2)));
+    assert_eq!(history.get(SIZE - 1), Some(&guess(3)));
+    assert_eq!(history.get(SIZE), None);
+
+    history.clear();
+    assert!(history.is_empty());
+    assert_eq!(history.get(0), None);
+}
@@ -0,0 +1,205 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use guess_the_number_core::brightness;
+use guess_the_number_core::framebuffer::{Blink, LEDMatrixFrame, Led};
+use guess_the_number_core::hal::{LEDMatrixDisplay, OutputPin};
+use guess_the_number_core::led_matrix::LEDMatrix;
+
+#[derive(Clone)]
+struct MockPin(Rc<Cell<bool>>);
+
+impl OutputPin for MockPin {
+    fn set_high(&mut self) {
+        self.0.set(true);
+    }
+
+    fn set_low(&mut self) {
+        self.0.set(false);
+    }
+}
+
+fn pin() -> (MockPin, Rc<Cell<bool>>) {
+    let state = Rc::new(Cell::new(false));
+    (MockPin(state.clone()), state)
+}
+
+struct Matrix<const W: usize, const H: usize> {
+    matrix: LEDMatrix<MockPin, W, H>,
+    anodes: Vec<Rc<Cell<bool>>>,
+    cathodes: Vec<Rc<Cell<bool>>>,
+}
+
+impl<const W: usize, const H: usize> Matrix<W, H> {
+    fn create() -> Matrix<W, H> {
+        let mut matrix = LEDMatrix::create();
+        let mut anodes = Vec::new();
+        let mut cathodes = Vec::new();
+        for _ in 0..W {
+            let (anode, state) = pin();
+            matrix.add_anode(anode);
+            anodes.push(state);
+        }
+        for _ in 0..H {
+            let (cathode, state) = pin();
+            matrix.add_cathode(cathode);
+            cathodes.push(state);
+        }
+
+        Matrix {
+            matrix,
+            anodes,
+            cathodes,
+        }
+    }
+
+    fn lit(&self) -> Vec<bool> {
+        self.anodes.iter().map(|anode| anode.get()).collect()
+    }
+
+    // the cathode of a row is pulled low while the row is on
+    fn rows_on(&self) -> Vec<bool> {
+        self.cathodes.iter().map(|cathode| !cathode.get()).collect()
+    }
+
+    // Runs a whole scan and notes the lit LEDs of every row
+    // at the start of its slot, as (x, y).
+    fn scan(&mut self) -> Vec<(usize, usize)> {
+        let mut seen = Vec::new();
+        for y in 0..H {
+            assert!(!self.matrix.step());
+
+            let rows_on = self.rows_on();
+            for (x, lit) in self.lit().iter().enumerate() {
+                if *lit {
+                    assert_eq!(rows_on.iter().filter(|on| **on).count(), 1);
+                    assert!(rows_on[y]);
+                    seen.push((x, y));
+                }
+            }
+
+            for tick in 1..brightness::SLOT_TICKS {
+                let last = self.matrix.step();
+                assert_eq!(last, y == H - 1 && tick == brightness::SLOT_TICKS - 1);
+            }
+        }
+
+        seen
+    }
+}
+
+// Lights the LEDs and checks they are scanned row by row,
+// each at its own column and row.
+fn check_scan<const W: usize, const H: usize>(leds: &[(usize, usize)]) {
+    let mut matrix = Matrix::<W, H>::create();
+    let mut frame = LEDMatrixFrame::<W, H>::create();
+    for (x, y) in leds {
+        frame.set(*x as u8, *y as u8);
+    }
+    matrix.matrix.set_frame(frame);
+
+    let mut expected = leds.to_vec();
+    expected.sort_by_key(|(x, y)| (*y, *x));
+    assert_eq!(matrix.scan(), expected);
+}
+
+#[test]
+fn scan_order_of_the_board() {
+    check_scan::<4, 2>(&[(0, 0), (3, 0), (1, 1), (2, 1)]);
+}
+
+#[test]
+fn scan_order_of_six_digits() {
+    check_scan::<6, 2>(&[(5, 0), (4, 1), (0, 1)]);
+    check_scan::<6, 2>(&[(0, 0), (1, 0), (2, 0), (3, 0), (4, 0), (5, 0)]);
+}
+
+#[test]
+fn scan_order_of_more_rows() {
+    check_scan::<3, 3>(&[(0, 0), (1, 1), (2, 2)]);
+    check_scan::<8, 8>(&[(7, 0), (0, 7), (3, 4), (4, 3)]);
+}
+
+#[test]
+fn empty_rows_stay_off() {
+    let mut matrix = Matrix::<6, 3>::create();
+    let mut frame = LEDMatrixFrame::<6, 3>::create();
+    frame.set(5, 2);
+    matrix.matrix.set_frame(frame);
+
+    matrix.matrix.step();
+    assert_eq!(matrix.rows_on(), [false, false, false]);
+}
+
+#[test]
+fn led_matrix_scans_the_frame() {
+    let mut matrix = Matrix::<4, 2>::create();
+    let mut frame = LEDMatrixFrame::create();
+    frame.set(0, 0);
+    frame.set(3, 1);
+    matrix.matrix.set_frame(frame);
+
+    // at full brightness the row is on for the whole slot
+    for _ in 0..brightness::SLOT_TICKS {
+        assert!(!matrix.matrix.step());
+        assert_eq!(matrix.lit(), [true, false, false, false]);
+        assert_eq!(matrix.rows_on(), [true, false]);
+    }
+
+    // second row ends the scan
+    for _ in 1..brightness::SLOT_TICKS {
+        assert!(!matrix.matrix.step());
+    }
+    assert!(matrix.matrix.step());
+    assert_eq!(matrix.lit(), [false, false, false, true]);
+    assert_eq!(matrix.rows_on(), [false, true]);
+}
+
+#[test]
+fn dim_led_matrix_is_turned_off_in_the_slot() {
+    let mut matrix = Matrix::<4, 2>::create();
+    let mut frame = LEDMatrixFrame::create();
+    frame.set(1, 0);
+    frame.set_brightness(0);
+    matrix.matrix.set_frame(frame);
+
+    matrix.matrix.step();
+    assert_eq!(matrix.rows_on(), [true, false]);
+    matrix.matrix.step();
+    assert_eq!(matrix.rows_on(), [false, false]);
+}
+
+#[test]
+fn dim_led_is_turned_off_before_the_row() {
+    let mut matrix = Matrix::<4, 2>::create();
+    let mut frame = LEDMatrixFrame::create();
+    frame.set(0, 0);
+    frame.set_led(1, 0, Led::create(0, Blink::Steady));
+    matrix.matrix.set_frame(frame);
+
+    matrix.matrix.step();
+    assert_eq!(matrix.lit(), [true, true, false, false]);
+    matrix.matrix.step();
+    assert_eq!(matrix.lit(), [true, false, false, false]);
+    assert_eq!(matrix.rows_on(), [true, false]);
+}
+
+#[test]
+fn blinking_led_follows_the_time() {
+    let mut matrix = Matrix::<4, 2>::create();
+    let mut frame = LEDMatrixFrame::create();
+    frame.set_led(3, 0, Led::create(brightness::MAX_LEVEL, Blink::Slow));
+    matrix.matrix.set_frame(frame);
+
+    matrix.matrix.step();
+    assert_eq!(matrix.lit(), [false, false, false, true]);
+
+    // the next scan in the dark half of the second
+    for _ in 1..2 * brightness::SLOT_TICKS {
+        matrix.matrix.step();
+    }
+    matrix.matrix.set_millis(700);
+    matrix.matrix.step();
+    assert_eq!(matrix.lit(), [false, false, false, false]);
+    assert_eq!(matrix.rows_on(), [false, false]);
+}
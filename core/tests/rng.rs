@@ -0,0 +1,105 @@
+// Reports the bias and the period of the generators,
+// run with `cargo test --test rng -- --nocapture` to see the numbers.
+
+use guess_the_number_core::rng::{self, Rng};
+
+const SEEDS: [[u8; 4]; 4] = [[125, 139, 45, 0], [1, 2, 3, 0], [0, 0, 1, 0], [200, 17, 99, 0]];
+
+// Steps the generator until it gets back to the state it started in,
+// None if that does not happen in limit steps.
+// Both generators are permutations of their state,
+// so every state lies on a cycle.
+fn period<R: Rng + Clone + PartialEq>(rng: &R, limit: u64) -> Option<u64> {
+    let start = rng.clone();
+    let mut rng = rng.clone();
+
+    for steps in 1..=limit {
+        rng.take_u8();
+        if rng == start {
+            return Some(steps);
+        }
+    }
+
+    None
+}
+
+// Chi-square statistic of the output bytes, 255 degrees of freedom.
+fn byte_bias<R: Rng>(rng: &mut R, samples: u64) -> f64 {
+    let mut counts = [0u64; 256];
+    for _ in 0..samples {
+        counts[usize::from(rng.take_u8())] += 1;
+    }
+
+    let expected = samples as f64 / 256.0;
+    counts.iter().map(|count| (*count as f64 - expected).powi(2) / expected).sum()
+}
+
+// Chi-square statistic of pairs of consecutive bytes, 65535 degrees of freedom.
+fn pair_bias<R: Rng>(rng: &mut R, samples: u64) -> f64 {
+    let mut counts = vec![0u64; 65536];
+    for _ in 0..samples {
+        counts[usize::from(rng.take_u16())] += 1;
+    }
+
+    let expected = samples as f64 / 65536.0;
+    counts.iter().map(|count| (*count as f64 - expected).powi(2) / expected).sum()
+}
+
+fn report_bias<R: Rng + Clone>(name: &str, seed: [u8; 4], rng: R) -> (f64, f64) {
+    let byte_bias = byte_bias(&mut rng.clone(), 1 << 20);
+    let pair_bias = pair_bias(&mut rng.clone(), 1 << 24);
+
+    println!(
+        "{:>10} seed {:?}: byte chi-square {:.1} (255 dof), pair chi-square {:.1} (65535 dof)",
+        name, seed, byte_bias, pair_bias
+    );
+
+    (byte_bias, pair_bias)
+}
+
+fn report_period<R: Rng + Clone + PartialEq>(name: &str, seed: [u8; 4], rng: R) -> Option<u64> {
+    let period = period(&rng, 1 << 32);
+    println!("{:>10} seed {:?}: period {:?}", name, seed, period);
+
+    period
+}
+
+#[test]
+fn xorshift32_bias() {
+    for seed in SEEDS {
+        let (byte_bias, pair_bias) = report_bias("xorshift32", seed, rng::XorShift32::init(seed));
+
+        // five standard deviations
+        assert!((byte_bias - 255.0).abs() < 5.0 * (2.0f64 * 255.0).sqrt());
+        assert!((pair_bias - 65535.0).abs() < 5.0 * (2.0f64 * 65535.0).sqrt());
+    }
+}
+
+#[test]
+fn xorshift32_zero_seed() {
+    let mut rng = rng::XorShift32::init([0; 4]);
+
+    assert!((0..16).any(|_| rng.take_u8() != 0));
+}
+
+// Only reported, the legacy generator does not pass for all seeds.
+#[test]
+fn legacy_bias() {
+    for seed in SEEDS {
+        report_bias("legacy", seed, rng::LegacyRng::init(seed[0], seed[1], seed[2]));
+    }
+}
+
+// Walks billions of states, about a minute with optimizations.
+// Run with `cargo test --test rng -- --ignored --nocapture`.
+#[test]
+#[ignore]
+fn periods() {
+    // all the non zero states are on one cycle, one seed is enough
+    let seed = SEEDS[0];
+    assert_eq!(report_period("xorshift32", seed, rng::XorShift32::init(seed)), Some((1 << 32) - 1));
+
+    for seed in SEEDS {
+        report_period("legacy", seed, rng::LegacyRng::init(seed[0], seed[1], seed[2]));
+    }
+}
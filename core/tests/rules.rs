@@ -0,0 +1,73 @@
+use guess_the_number_core::game::DIGITS;
+use guess_the_number_core::rng;
+use guess_the_number_core::rules::GameRules;
+
+const SAMPLES: usize = 10000;
+
+fn check_generated<const D: usize>(rules: GameRules) {
+    let mut rng = rng::XorShift32::init([125, 139, 45, 0]);
+    let length = rules.length();
+    let mut leading_zero_seen = false;
+
+    for _ in 0..SAMPLES {
+        let number: [u8; D] = rules.generate_number(&mut rng);
+
+        for (i, digit) in number.iter().enumerate() {
+            if i >= length {
+                assert_eq!(*digit, 0);
+            } else {
+                assert!(*digit < rules.alphabet, "{:?} with {:?}", number, rules);
+            }
+        }
+
+        if number[length - 1] == 0 {
+            leading_zero_seen = true;
+        }
+
+        if rules.unique_digits {
+            for i in 0..length {
+                assert!(!number[i + 1..length].contains(&number[i]), "{:?} with {:?}", number, rules);
+            }
+        }
+    }
+
+    assert_eq!(leading_zero_seen, rules.leading_zero, "{:?}", rules);
+}
+
+#[test]
+fn default_rules_are_valid() {
+    assert!(GameRules::default().valid(DIGITS));
+}
+
+#[test]
+fn invalid_rules_are_rejected() {
+    let rules = GameRules::default();
+
+    assert!(!GameRules { length: 0, ..rules }.valid(DIGITS));
+    assert!(!GameRules { length: 5, ..rules }.valid(DIGITS));
+    assert!(!GameRules { alphabet: 11, ..rules }.valid(DIGITS));
+    assert!(!GameRules { alphabet: 3, unique_digits: true, ..rules }.valid(DIGITS));
+
+    // longer codes on longer displays
+    assert!(GameRules { length: 5, ..rules }.valid(6));
+}
+
+#[test]
+fn generated_numbers_follow_rules() {
+    for unique_digits in [false, true] {
+        for leading_zero in [false, true] {
+            for alphabet in 4..=10 {
+                for length in 1..=6 {
+                    let rules = GameRules { unique_digits, leading_zero, alphabet, length };
+
+                    if rules.valid(DIGITS) {
+                        check_generated::<DIGITS>(rules);
+                    }
+                    if rules.valid(6) {
+                        check_generated::<6>(rules);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use guess_the_number_core::scoring::{self, Mark};
+
+const DIGITS: usize = 4;
+
+fn digits(number: u16) -> [u8; DIGITS] {
+    let mut digits = [0; DIGITS];
+    let mut number = number;
+    for digit in digits.iter_mut() {
+        *digit = (number % 10) as u8;
+        number /= 10;
+    }
+    digits
+}
+
+// Textbook Mastermind scoring, (correct, misplaced)
+// from the counts of every digit value.
+fn reference_score(secret: &[u8; DIGITS], guess: &[u8; DIGITS]) -> (usize, usize) {
+    let mut secret_counts = [0usize; 10];
+    let mut guess_counts = [0usize; 10];
+    let mut correct = 0;
+
+    for i in 0..DIGITS {
+        if secret[i] == guess[i] {
+            correct += 1;
+        }
+        secret_counts[usize::from(secret[i])] += 1;
+        guess_counts[usize::from(guess[i])] += 1;
+    }
+
+    let common: usize = (0..10).map(|d| secret_counts[d].min(guess_counts[d])).sum();
+    (correct, common - correct)
+}
+
+#[test]
+fn duplicate_digits_are_counted_once() {
+    let secret = [3, 2, 1, 1];
+    let guess = [1, 1, 1, 1];
+    let mut marks = [Mark::Absent; DIGITS];
+
+    scoring::score(&secret, &guess, &mut marks);
+
+    assert_eq!(marks, [Mark::Absent, Mark::Absent, Mark::Correct, Mark::Correct]);
+}
+
+#[test]
+fn misplaced_digits_consume_the_secret() {
+    let secret = [1, 2, 3, 4];
+    let guess = [2, 2, 2, 1];
+    let mut marks = [Mark::Absent; DIGITS];
+
+    scoring::score(&secret, &guess, &mut marks);
+
+    assert_eq!(marks, [Mark::Absent, Mark::Correct, Mark::Absent, Mark::Misplaced]);
+}
+
+#[test]
+fn all_pairs_match_reference() {
+    let mut marks = [Mark::Absent; DIGITS];
+
+    for secret in 0..10000 {
+        let secret = digits(secret);
+
+        for guess in 0..10000 {
+            let guess = digits(guess);
+            scoring::score(&secret, &guess, &mut marks);
+
+            let mut correct = 0;
+            let mut misplaced = 0;
+            for i in 0..DIGITS {
+                match marks[i] {
+                    Mark::Correct => {
+                        assert_eq!(secret[i], guess[i]);
+                        correct += 1;
+                    }
+                    Mark::Misplaced => {
+                        assert_ne!(secret[i], guess[i]);
+                        assert!(secret.contains(&guess[i]));
+                        misplaced += 1;
+                    }
+                    Mark::Absent => assert_ne!(secret[i], guess[i]),
+                }
+            }
+
+            assert_eq!(
+                (correct, misplaced),
+                reference_score(&secret, &guess),
+                "secret {:?}, guess {:?}",
+                secret,
+                guess
+            );
+        }
+    }
+}
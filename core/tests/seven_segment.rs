@@ -0,0 +1,95 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use guess_the_number_core::filled_sipo::FilledSipo;
+use guess_the_number_core::glyph::Glyph;
+use guess_the_number_core::hal::OutputPin;
+use guess_the_number_core::seven_segment::SevenSegment;
+use guess_the_number_core::sipo::Sipo;
+
+// A chain of 595s, the bits shifted in first end up at the top.
+#[derive(Default)]
+struct Chain {
+    ser: bool,
+    shifted: u32,
+    latched: u32,
+}
+
+#[derive(Copy, Clone)]
+enum Role {
+    Srclk,
+    Srclr,
+    Ser,
+    Rclk,
+}
+
+struct ChainPin(Rc<RefCell<Chain>>, Role);
+
+impl OutputPin for ChainPin {
+    fn set_high(&mut self) {
+        let mut chain = self.0.borrow_mut();
+        match self.1 {
+            Role::Srclk => chain.shifted = (chain.shifted << 1) | u32::from(chain.ser),
+            Role::Rclk => chain.latched = chain.shifted,
+            Role::Ser => chain.ser = true,
+            Role::Srclr => {}
+        }
+    }
+
+    fn set_low(&mut self) {
+        let mut chain = self.0.borrow_mut();
+        match self.1 {
+            Role::Srclr => chain.shifted = 0,
+            Role::Ser => chain.ser = false,
+            _ => {}
+        }
+    }
+}
+
+fn shift_registers<const N: usize>() -> (FilledSipo<ChainPin, N>, Rc<RefCell<Chain>>) {
+    let chain = Rc::new(RefCell::new(Chain::default()));
+    let pin = |role| ChainPin(chain.clone(), role);
+    let sipo = Sipo::create(pin(Role::Srclk), pin(Role::Srclr), pin(Role::Ser), pin(Role::Rclk));
+    (FilledSipo::create(sipo), chain)
+}
+
+// Latched outputs after the digit is pushed out.
+fn outputs<const D: usize, const N: usize>(seven_segment: &SevenSegment<D>, digit: u8, digit_index: usize) -> u32 {
+    let (mut sipo, chain) = shift_registers::<N>();
+    assert!(seven_segment.fill_glyph(&mut sipo, Glyph::digit(digit), digit_index));
+    sipo.push_block();
+
+    let latched = chain.borrow().latched;
+    latched
+}
+
+#[test]
+fn four_digits_on_two_registers() {
+    let seven_segment = SevenSegment::<4>::create(true, false);
+    let segments = u32::from(Glyph::digit(8).segments());
+
+    // common anode, the segments are active low
+    assert_eq!(outputs::<4, 2>(&seven_segment, 8, 3), (0b0001 << 8) | (!segments & 0xFF));
+    assert_eq!(outputs::<4, 2>(&seven_segment, 8, 0), (0b1000 << 8) | (!segments & 0xFF));
+}
+
+#[test]
+fn selectors_take_the_free_bits() {
+    // without the decimal point, eight digits fit on two registers
+    let seven_segment = SevenSegment::<8>::create(false, true);
+    let segments = u32::from(Glyph::digit(1).segments());
+    let selectors = 0x1FF & !(1 << 7);
+    assert_eq!(outputs::<8, 2>(&seven_segment, 1, 0), (selectors << 7) | segments);
+
+    // six digits on three registers
+    let seven_segment = SevenSegment::<6>::create(true, true);
+    let selectors = 0xFFFF & !(1 << 2);
+    assert_eq!(outputs::<6, 3>(&seven_segment, 1, 3), (selectors << 8) | segments);
+}
+
+#[test]
+fn digits_outside_are_not_filled() {
+    let seven_segment = SevenSegment::<3>::create(true, true);
+    let (mut sipo, _) = shift_registers::<2>();
+    assert!(!seven_segment.fill_glyph(&mut sipo, Glyph::digit(1), 3));
+}
@@ -0,0 +1,266 @@
+use guess_the_number_core::rules::GameRules;
+use guess_the_number_core::scores::HighScores;
+use guess_the_number_core::settings::{InputMode, Settings};
+use guess_the_number_core::stats::Statistics;
+use guess_the_number_core::storage::{self, Eeprom, Record, Storage};
+
+const CAPACITY: usize = 512;
+
+// EEPROM of the ATmega8 in memory, counts the writes of every cell.
+struct MemoryEeprom {
+    data: Vec<u8>,
+    writes: Vec<u32>,
+}
+
+impl MemoryEeprom {
+    fn erased() -> MemoryEeprom {
+        MemoryEeprom {
+            data: vec![0xFF; CAPACITY],
+            writes: vec![0; CAPACITY],
+        }
+    }
+
+    fn with_legacy_seed(seed: &[u8]) -> MemoryEeprom {
+        let mut eeprom = MemoryEeprom::erased();
+        eeprom.data[..seed.len()].copy_from_slice(seed);
+        eeprom
+    }
+}
+
+impl Eeprom for MemoryEeprom {
+    fn capacity(&self) -> u16 {
+        self.data.len() as u16
+    }
+
+    fn read(&self, offset: u16, buffer: &mut [u8]) {
+        let offset = usize::from(offset);
+        buffer.copy_from_slice(&self.data[offset..offset + buffer.len()]);
+    }
+
+    fn write(&mut self, offset: u16, data: &[u8]) {
+        let offset = usize::from(offset);
+        for (i, byte) in data.iter().enumerate() {
+            self.data[offset + i] = *byte;
+            self.writes[offset + i] += 1;
+        }
+    }
+}
+
+fn record(seed: u32) -> Record {
+    Record {
+        seed: seed.to_le_bytes(),
+        ..Record::default()
+    }
+}
+
+fn reload(eeprom: MemoryEeprom) -> (Storage<MemoryEeprom>, Record) {
+    let mut storage = Storage::create(eeprom);
+    let record = storage.load();
+    (storage, record)
+}
+
+#[test]
+fn crc_check_value() {
+    assert_eq!(storage::crc16(b"123456789"), 0x29B1);
+}
+
+#[test]
+fn erased_eeprom_gives_defaults() {
+    let mut storage = Storage::create(MemoryEeprom::erased());
+
+    assert_eq!(storage.load(), Record::default());
+}
+
+#[test]
+fn saved_record_is_loaded() {
+    let mut storage = Storage::create(MemoryEeprom::erased());
+    storage.load();
+    storage.save(&record(0x1234_5678));
+
+    let (_, loaded) = reload(storage.release());
+    assert_eq!(loaded, record(0x1234_5678));
+}
+
+#[test]
+fn newest_record_wins_across_sequence_wrap() {
+    let mut storage = Storage::create(MemoryEeprom::erased());
+    storage.load();
+
+    // more saves than the sequence number can count
+    for i in 0..1000 {
+        storage.save(&record(i));
+
+        let (reloaded, loaded) = reload(storage.release());
+        assert_eq!(loaded, record(i), "save {}", i);
+        storage = reloaded;
+    }
+}
+
+#[test]
+fn writes_are_spread_over_the_slots() {
+    let mut storage = Storage::create(MemoryEeprom::erased());
+    storage.load();
+
+    let saves = 800;
+    for i in 0..saves {
+        storage.save(&record(i));
+    }
+
+    let slots = (CAPACITY / storage::SLOT_SIZE) as u32;
+    let writes = storage.release().writes;
+    assert_eq!(*writes.iter().max().unwrap(), saves / slots);
+    assert_eq!(*writes.iter().min().unwrap(), saves / slots);
+}
+
+#[test]
+fn corrupted_slot_falls_back_to_previous_record() {
+    let mut storage = Storage::create(MemoryEeprom::erased());
+    storage.load();
+    storage.save(&record(1));
+    storage.save(&record(2));
+
+    // the second save went to the second slot, as if the power failed while writing it
+    let mut eeprom = storage.release();
+    eeprom.data[storage::SLOT_SIZE + 5] ^= 0x10;
+
+    let (_, loaded) = reload(eeprom);
+    assert_eq!(loaded, record(1));
+}
+
+#[test]
+fn save_after_corrupted_slot_is_newest() {
+    let mut storage = Storage::create(MemoryEeprom::erased());
+    storage.load();
+    storage.save(&record(1));
+    storage.save(&record(2));
+    let mut eeprom = storage.release();
+    eeprom.data[storage::SLOT_SIZE + 5] ^= 0x10;
+
+    let (mut storage, _) = reload(eeprom);
+    storage.save(&record(3));
+
+    let (_, loaded) = reload(storage.release());
+    assert_eq!(loaded, record(3));
+}
+
+#[test]
+fn future_version_is_ignored() {
+    let mut storage = Storage::create(MemoryEeprom::erased());
+    storage.load();
+    storage.save(&record(1));
+    storage.save(&record(2));
+
+    // a record written by a newer firmware, with a valid checksum
+    let offset = storage::SLOT_SIZE;
+    let mut eeprom = storage.release();
+    let data = &mut eeprom.data;
+    data[offset + 1] = storage::VERSION + 1;
+    let crc = storage::crc16(&data[offset..offset + storage::SLOT_SIZE - 2]);
+    data[offset + storage::SLOT_SIZE - 2..offset + storage::SLOT_SIZE]
+        .copy_from_slice(&crc.to_le_bytes());
+
+    let (_, loaded) = reload(eeprom);
+    assert_eq!(loaded, record(1));
+}
+
+#[test]
+fn legacy_seed_is_migrated() {
+    // the original firmware wrote three seed bytes, the erased fourth stays 0xFF
+    let mut storage = Storage::create(MemoryEeprom::with_legacy_seed(&[7, 8, 9]));
+    let loaded = storage.load();
+    assert_eq!(loaded.seed, [7, 8, 9, 0xFF]);
+
+    storage.save(&loaded);
+    let (_, reloaded) = reload(storage.release());
+    assert_eq!(reloaded, loaded);
+}
+
+#[test]
+fn zero_legacy_seed_gives_defaults() {
+    let mut storage = Storage::create(MemoryEeprom::with_legacy_seed(&[0, 0, 0, 0]));
+
+    assert_eq!(storage.load(), Record::default());
+}
+
+#[test]
+fn high_scores_are_kept() {
+    let mut storage = Storage::create(MemoryEeprom::erased());
+    let mut saved = storage.load();
+    saved.high_scores = HighScores {
+        best_attempts: Some(3),
+        best_time: Some(61_500),
+    };
+    storage.save(&saved);
+
+    let (_, loaded) = reload(storage.release());
+    assert_eq!(loaded, saved);
+}
+
+#[test]
+fn version_1_record_gets_default_high_scores() {
+    // magic, version 1, sequence 0, the seed, the rest of the payload zeros
+    let mut slot = [0u8; storage::SLOT_SIZE];
+    slot[..7].copy_from_slice(&[0xA5, 1, 0, 1, 2, 3, 4]);
+    let crc = storage::crc16(&slot[..storage::SLOT_SIZE - 2]);
+    slot[storage::SLOT_SIZE - 2..].copy_from_slice(&crc.to_le_bytes());
+
+    let mut eeprom = MemoryEeprom::erased();
+    eeprom.data[..storage::SLOT_SIZE].copy_from_slice(&slot);
+
+    let (_, loaded) = reload(eeprom);
+    assert_eq!(loaded.seed, [1, 2, 3, 4]);
+    assert_eq!(loaded.high_scores, HighScores::default());
+    assert_eq!(loaded.statistics, Statistics::default());
+    assert_eq!(loaded.settings, Settings::default());
+}
+
+#[test]
+fn statistics_are_kept() {
+    let mut storage = Storage::create(MemoryEeprom::erased());
+    let mut saved = storage.load();
+    saved.statistics = Statistics {
+        played: 70_000,
+        won: 65_000,
+        lost: 3_000,
+        histogram: [1, 2, 3, 4, 5, 6, 7, 8, 65_535],
+    };
+    storage.save(&saved);
+
+    let (_, loaded) = reload(storage.release());
+    assert_eq!(loaded, saved);
+}
+
+#[test]
+fn settings_are_kept() {
+    let mut storage = Storage::create(MemoryEeprom::erased());
+    let mut saved = storage.load();
+    saved.settings = Settings {
+        seven_segment_brightness: 2,
+        led_matrix_brightness: 0,
+        input_mode: InputMode::Cursor,
+        max_attempts: 10,
+        rules: GameRules {
+            unique_digits: true,
+            leading_zero: true,
+            alphabet: 6,
+            length: 3,
+        },
+    };
+    storage.save(&saved);
+
+    let (_, loaded) = reload(storage.release());
+    assert_eq!(loaded, saved);
+}
+
+#[test]
+fn damaged_settings_get_defaults() {
+    let mut storage = Storage::create(MemoryEeprom::erased());
+    let mut saved = storage.load();
+    saved.settings.seven_segment_brightness = 200;
+    saved.settings.led_matrix_brightness = 3;
+    storage.save(&saved);
+
+    let (_, loaded) = reload(storage.release());
+    assert_eq!(loaded.settings.seven_segment_brightness, Settings::default().seven_segment_brightness);
+    assert_eq!(loaded.settings.led_matrix_brightness, 3);
+}
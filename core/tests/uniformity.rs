@@ -0,0 +1,110 @@
+use guess_the_number_core::game::DIGITS;
+use guess_the_number_core::rng::{self, Rng};
+use guess_the_number_core::rules::GameRules;
+
+fn create_rng() -> rng::XorShift32 {
+    rng::XorShift32::init([125, 139, 45, 0])
+}
+
+// Chi-square statistic of the counts against a uniform distribution.
+fn chi_square(counts: &[u64]) -> f64 {
+    let total: u64 = counts.iter().sum();
+    let expected = total as f64 / counts.len() as f64;
+
+    counts
+        .iter()
+        .map(|count| {
+            let difference = *count as f64 - expected;
+            difference * difference / expected
+        })
+        .sum()
+}
+
+// For this many degrees of freedom the statistic is close to normal
+// with mean dof and variance 2 * dof, allow five standard deviations.
+fn assert_uniform(counts: &[u64]) {
+    let dof = (counts.len() - 1) as f64;
+    let limit = 5.0 * (2.0 * dof).sqrt();
+    let statistic = chi_square(counts);
+
+    assert!(
+        (statistic - dof).abs() < limit,
+        "chi-square {} for {} degrees of freedom",
+        statistic,
+        dof
+    );
+}
+
+fn number_index(number: &[u8; DIGITS]) -> usize {
+    number.iter().rev().fold(0, |index, digit| index * 10 + usize::from(*digit))
+}
+
+#[test]
+fn take_below_is_uniform() {
+    let mut rng = create_rng();
+
+    for n in [2u8, 3, 6, 7, 10, 100, 255] {
+        let mut counts = vec![0u64; n.into()];
+        for _ in 0..1_000_000 {
+            counts[usize::from(rng.take_below(n))] += 1;
+        }
+
+        assert_uniform(&counts);
+    }
+}
+
+#[test]
+fn take_range_stays_in_range() {
+    let mut rng = create_rng();
+
+    for _ in 0..10000 {
+        let value = rng.take_range(1, 9);
+        assert!((1..=9).contains(&value));
+    }
+}
+
+#[test]
+fn take_range_takes_the_ends() {
+    let mut rng = create_rng();
+
+    for value in [0, 7, 255] {
+        assert_eq!(rng.take_range(value, value), value);
+    }
+    assert_eq!(rng.take_range(9, 1), 9);
+    assert_eq!(rng.take_range(255, 0), 255);
+
+    // the whole range is uniform as well
+    let mut counts = vec![0u64; 256];
+    for _ in 0..1_000_000 {
+        counts[usize::from(rng.take_range(0, 255))] += 1;
+    }
+    assert_uniform(&counts);
+}
+
+#[test]
+fn every_secret_is_equally_likely() {
+    let rules = GameRules::default();
+    let mut rng = create_rng();
+    let mut counts = vec![0u64; 10000];
+
+    for _ in 0..9_000_000 {
+        counts[number_index(&rules.generate_number(&mut rng))] += 1;
+    }
+
+    // no leading zero, only 1000..=9999 are possible
+    assert!(counts[..1000].iter().all(|count| *count == 0));
+    assert_uniform(&counts[1000..]);
+}
+
+#[test]
+fn every_secret_is_equally_likely_with_leading_zero() {
+    let rules = GameRules { leading_zero: true, ..GameRules::default() };
+    let mut rng = create_rng();
+    let mut counts = vec![0u64; 10000];
+
+    for _ in 0..10_000_000 {
+        counts[number_index(&rules.generate_number(&mut rng))] += 1;
+    }
+
+    assert_uniform(&counts);
+}
@@ -0,0 +1,150 @@
+use core::mem::MaybeUninit;
+use core::ptr;
+
+use atmega_hal::pac;
+use atmega_hal::port::{Pin, mode};
+
+use guess_the_number_core::{entropy, hal, storage};
+
+// The pin traits live in the core crate, so the avr-hal pins
+// are wrapped to be able to implement them here.
+
+pub struct Input(pub Pin<mode::Input>);
+
+impl hal::InputPin for Input {
+    #[inline]
+    fn is_low(&self) -> bool {
+        self.0.is_low()
+    }
+}
+
+pub struct Output(pub Pin<mode::Output>);
+
+impl hal::OutputPin for Output {
+    #[inline]
+    fn set_high(&mut self) {
+        self.0.set_high();
+    }
+
+    #[inline]
+    fn set_low(&mut self) {
+        self.0.set_low();
+    }
+}
+
+// 512 bytes on the ATmega8
+const EEPROM_CAPACITY: u16 = 512;
+
+pub struct Eeprom(pub atmega_hal::Eeprom);
+
+impl storage::Eeprom for Eeprom {
+    #[inline]
+    fn capacity(&self) -> u16 {
+        EEPROM_CAPACITY
+    }
+
+    // Accesses outside of the EEPROM are a bug of the storage, ignored here.
+    fn read(&self, offset: u16, buffer: &mut [u8]) {
+        let _ = self.0.read(offset, buffer);
+    }
+
+    // Writing a byte takes about 8.5 ms and wears the cell,
+    // the unchanged bytes are skipped.
+    fn write(&mut self, offset: u16, data: &[u8]) {
+        for (i, byte) in data.iter().enumerate() {
+            let address = offset + i as u16;
+            let mut current = [0u8];
+            let _ = self.0.read(address, &mut current);
+
+            if current[0] != *byte {
+                let _ = self.0.write(address, &[*byte]);
+            }
+        }
+    }
+}
+
+const ADMUX_REFS_AVCC: u8 = 1 << 6;
+const ADCSRA_ADEN: u8 = 1 << 7;
+const ADCSRA_ADSC: u8 = 1 << 6;
+const ADCSRA_ADPS_128: u8 = 0b111;
+
+const MCUCSR_WDRF: u8 = 1 << 3;
+const WDTCR_WDCE: u8 = 1 << 4;
+const WDTCR_WDE: u8 = 1 << 3;
+
+// ADC readings of a floating pin, the low bits are noise.
+// All the ADC pins of the PDIP package drive the LED matrix,
+// so the pin has to be sampled before it is set as an output.
+pub struct AdcNoise<'a> {
+    adc: &'a pac::ADC,
+}
+
+impl<'a> AdcNoise<'a> {
+    pub fn create(adc: &'a pac::ADC, channel: u8) -> AdcNoise<'a> {
+        adc.admux.write(|w| unsafe { w.bits(ADMUX_REFS_AVCC | channel) });
+        adc.adcsra.write(|w| unsafe { w.bits(ADCSRA_ADEN | ADCSRA_ADPS_128) });
+
+        AdcNoise {
+            adc
+        }
+    }
+}
+
+impl<'a> entropy::EntropySource for AdcNoise<'a> {
+    fn sample(&mut self) -> u16 {
+        self.adc.adcsra.modify(|r, w| unsafe { w.bits(r.bits() | ADCSRA_ADSC) });
+        while self.adc.adcsra.read().bits() & ADCSRA_ADSC != 0 {}
+
+        self.adc.adc.read().bits()
+    }
+}
+
+impl<'a> Drop for AdcNoise<'a> {
+    fn drop(&mut self) {
+        self.adc.adcsra.write(|w| unsafe { w.bits(0) });
+    }
+}
+
+// Not cleared on startup, so it survives the watchdog reset.
+#[link_section = ".noinit"]
+static mut WATCHDOG_COUNTER: MaybeUninit<u16> = MaybeUninit::uninit();
+
+// The watchdog runs from its own oscillator, so counting loop iterations
+// until it fires gives a different number on every boot.
+// The watchdog of the ATmega8 can only reset the chip, not interrupt it,
+// so the first call never returns. After the reset the count is returned.
+pub fn watchdog_jitter(cpu: &pac::CPU, wdt: &pac::WDT) -> u16 {
+    unsafe {
+        let counter = WATCHDOG_COUNTER.as_mut_ptr();
+
+        if cpu.mcucsr.read().bits() & MCUCSR_WDRF != 0 {
+            cpu.mcucsr.write(|w| w.bits(0));
+            wdt.wdtcr.write(|w| w.bits(WDTCR_WDCE | WDTCR_WDE));
+            wdt.wdtcr.write(|w| w.bits(0));
+
+            return ptr::read_volatile(counter);
+        }
+
+        ptr::write_volatile(counter, 0);
+        // the shortest timeout, about 16 ms
+        wdt.wdtcr.write(|w| w.bits(WDTCR_WDE));
+
+        loop {
+            ptr::write_volatile(counter, ptr::read_volatile(counter).wrapping_add(1));
+        }
+    }
+}
+
+// Read through lpm, declared with progmem_text!.
+pub use guess_the_number_core::progmem::ProgmemText;
+
+// A static avr::ProgmemText, the text does not take any RAM.
+macro_rules! progmem_text {
+    ($name:ident = $text:literal) => {
+        static $name: avr::ProgmemText = {
+            #[link_section = ".progmem.data"]
+            static BYTES: [u8; $text.len()] = *$text;
+            avr::ProgmemText(&BYTES)
+        };
+    };
+}
@@ -0,0 +1,115 @@
+use core::cell::{Cell, RefCell};
+
+use atmega_hal::pac;
+use avr_device::interrupt::{self, Mutex};
+
+use guess_the_number_core::{clock, filled_seven_segment, framebuffer, led_matrix};
+
+use super::avr;
+
+// Timer2 in the CTC mode interrupts with a fixed rate,
+// scans the displays out, counts the milliseconds
+// and paces the game loop.
+
+const TCCR2_WGM21: u8 = 1 << 3;
+const TCCR2_CS21: u8 = 1 << 1; // clk / 8
+const TIMSK_OCIE2: u8 = 1 << 7;
+// 16 MHz / 8 / 200 = 10 kHz
+const TIMER2_COMPARE: u8 = 199;
+const TICKS_PER_MILLISECOND: u8 = 10;
+
+// four digits behind two shift registers
+pub const DIGITS: usize = 4;
+pub const SHIFT_REGISTERS: usize = 2;
+pub type SevenSegment = filled_seven_segment::FilledSevenSegment<avr::Output, DIGITS, SHIFT_REGISTERS>;
+pub type SevenSegmentFrame = framebuffer::SevenSegmentFrame<DIGITS>;
+// a red and a yellow LED for each digit
+pub const LED_MATRIX_WIDTH: usize = 4;
+pub const LED_MATRIX_HEIGHT: usize = 2;
+pub type LEDMatrix = led_matrix::LEDMatrix<avr::Output, LED_MATRIX_WIDTH, LED_MATRIX_HEIGHT>;
+pub type LEDMatrixFrame = framebuffer::LEDMatrixFrame<LED_MATRIX_WIDTH, LED_MATRIX_HEIGHT>;
+
+// The drivers are owned by the interrupt once the scanning starts,
+// the game loop only hands them new frames.
+struct Displays {
+    seven_segment: SevenSegment,
+    led_matrix: LEDMatrix,
+}
+
+static DISPLAYS: Mutex<RefCell<Option<Displays>>> = Mutex::new(RefCell::new(None));
+static TICKS: Mutex<Cell<u8>> = Mutex::new(Cell::new(0));
+static MILLIS: Mutex<Cell<u32>> = Mutex::new(Cell::new(0));
+
+#[avr_device::interrupt(atmega8)]
+fn TIMER2_COMP() {
+    interrupt::free(|cs| {
+        // counts the ticks of a millisecond
+        let ticks = TICKS.borrow(cs);
+        let tick = (ticks.get() + 1) % TICKS_PER_MILLISECOND;
+        ticks.set(tick);
+
+        let millis = MILLIS.borrow(cs);
+        let millisecond = tick == 0;
+        if millisecond {
+            millis.set(millis.get().wrapping_add(1));
+        }
+
+        if let Some(displays) = DISPLAYS.borrow(cs).borrow_mut().as_mut() {
+            if millisecond {
+                displays.led_matrix.set_millis(millis.get());
+            }
+
+            // a digit or a row takes brightness::SLOT_TICKS ticks,
+            // the seven segment display is refreshed at about 156 Hz, the matrix at 312 Hz
+            displays.seven_segment.step();
+            displays.led_matrix.step();
+        }
+    });
+}
+
+// Takes the displays over and starts the timer.
+// The interrupts have to be enabled afterwards.
+pub fn start(tc2: &pac::TC2, seven_segment: SevenSegment, led_matrix: LEDMatrix) -> Millis {
+    interrupt::free(|cs| {
+        DISPLAYS.borrow(cs).replace(Some(Displays {
+            seven_segment,
+            led_matrix,
+        }));
+    });
+
+    tc2.ocr2.write(|w| unsafe { w.bits(TIMER2_COMPARE) });
+    tc2.tccr2.write(|w| unsafe { w.bits(TCCR2_WGM21 | TCCR2_CS21) });
+    tc2.timsk.modify(|r, w| unsafe { w.bits(r.bits() | TIMSK_OCIE2) });
+
+    Millis
+}
+
+// Copies the frames to the drivers at once,
+// so a half drawn frame is never shown.
+pub fn show(seven_segment: &SevenSegmentFrame, led_matrix: &LEDMatrixFrame) {
+    interrupt::free(|cs| {
+        if let Some(displays) = DISPLAYS.borrow(cs).borrow_mut().as_mut() {
+            displays.seven_segment.set_frame(*seven_segment);
+            displays.led_matrix.set_frame(*led_matrix);
+        }
+    });
+}
+
+// Blocks until the next tick, one game step per tick
+// keeps the timing of the buttons and the animations
+// independent of how long the steps take.
+pub fn wait_tick() {
+    let last = interrupt::free(|cs| TICKS.borrow(cs).get());
+    while interrupt::free(|cs| TICKS.borrow(cs).get()) == last {}
+}
+
+// Milliseconds counted by the interrupt.
+pub struct Millis;
+
+impl clock::Clock for Millis {
+    // The counter has four bytes, it is read with the interrupts disabled.
+    #[inline]
+    fn millis(&self) -> u32 {
+        interrupt::free(|cs| MILLIS.borrow(cs).get())
+    }
+}
@@ -0,0 +1,22 @@
+use std::time::Instant;
+
+use guess_the_number_core::clock;
+
+// Milliseconds since the start of the simulator.
+pub struct SimClock {
+    started: Instant,
+}
+
+impl SimClock {
+    pub fn create() -> SimClock {
+        SimClock {
+            started: Instant::now(),
+        }
+    }
+}
+
+impl clock::Clock for SimClock {
+    fn millis(&self) -> u32 {
+        self.started.elapsed().as_millis() as u32
+    }
+}
@@ -0,0 +1,106 @@
+use guess_the_number_core::brightness;
+use guess_the_number_core::framebuffer;
+
+// The game draws into the same frames as on the board,
+// here they are rendered to the terminal instead of scanned out.
+
+const LED_MATRIX_WIDTH: usize = 4;
+const LED_MATRIX_HEIGHT: usize = 2;
+pub type LEDMatrixFrame = framebuffer::LEDMatrixFrame<LED_MATRIX_WIDTH, LED_MATRIX_HEIGHT>;
+pub const DIGITS: usize = 4;
+pub type SevenSegmentFrame = framebuffer::SevenSegmentFrame<DIGITS>;
+
+const SEGMENT: (u8, u8, u8) = (255, 60, 40);
+const RED: (u8, u8, u8) = (255, 30, 30);
+const YELLOW: (u8, u8, u8) = (255, 200, 0);
+const DIM: &str = "\x1b[90m";
+const RESET: &str = "\x1b[0m";
+
+const DOT_SEGMENT: u8 = 0b10000000;
+
+// The color scaled by the ticks of a scan slot it is lit,
+// the darkest level stays visible in the terminal.
+fn shade(color: (u8, u8, u8), lit_ticks: u8) -> String {
+    let scale = |value: u8| {
+        let slot = u32::from(brightness::SLOT_TICKS);
+        u32::from(value) * (slot + 2 * u32::from(lit_ticks)) / (3 * slot)
+    };
+
+    format!("\x1b[38;2;{};{};{}m", scale(color.0), scale(color.1), scale(color.2))
+}
+
+fn segments(frame: &SevenSegmentFrame, digit_index: usize) -> u8 {
+    if frame.hidden(digit_index) {
+        return 0;
+    }
+
+    let segments = match frame.glyph(digit_index) {
+        Some(glyph) => glyph.segments(),
+        None => 0,
+    };
+
+    if frame.dot(digit_index) {
+        segments | DOT_SEGMENT
+    } else {
+        segments
+    }
+}
+
+// Renders the digits as three lines of ASCII art,
+// digit with index 0 is the rightmost one, as on the board.
+pub fn render_seven_segment(frame: &SevenSegmentFrame) -> [String; 3] {
+    let color = shade(SEGMENT, brightness::lit_ticks(frame.brightness()));
+    let mut lines = [color.clone(), color.clone(), color];
+
+    for digit_index in (0..DIGITS).rev() {
+        let segments = segments(frame, digit_index);
+        let on = |bit: u8, c: char| if segments & (1 << bit) != 0 { c } else { ' ' };
+
+        //     HGFEDCBA
+        lines[0].push(' ');
+        lines[0].push(on(0, '_'));
+        lines[0].push_str("  ");
+
+        lines[1].push(on(5, '|'));
+        lines[1].push(on(6, '_'));
+        lines[1].push(on(1, '|'));
+        lines[1].push(' ');
+
+        lines[2].push(on(4, '|'));
+        lines[2].push(on(3, '_'));
+        lines[2].push(on(2, '|'));
+        lines[2].push(on(7, '.'));
+    }
+
+    for line in lines.iter_mut() {
+        line.push_str(RESET);
+    }
+    lines
+}
+
+// Renders one line per row, the first row is the red one
+// (correct position), the second one yellow (incorrect position).
+// Columns are aligned with the digits above them,
+// the blinking LEDs follow the milliseconds.
+pub fn render_led_matrix(frame: &LEDMatrixFrame, millis: u32) -> [String; LED_MATRIX_HEIGHT] {
+    let mut lines = [String::new(), String::new()];
+
+    for (y, line) in lines.iter_mut().enumerate() {
+        let color = if y == 0 { RED } else { YELLOW };
+
+        for x in (0..LED_MATRIX_WIDTH).rev() {
+            let led = frame.led(x as u8, y as u8).filter(|led| led.blink.visible(millis));
+            if let Some(led) = led {
+                let lit_ticks = brightness::scaled_lit_ticks(frame.brightness(), led.intensity);
+                line.push_str(&shade(color, lit_ticks));
+                line.push_str(" @  ");
+            } else {
+                line.push_str(DIM);
+                line.push_str(" .  ");
+            }
+        }
+        line.push_str(RESET);
+    }
+
+    lines
+}
@@ -0,0 +1,45 @@
+use std::fs;
+use std::path::PathBuf;
+
+use guess_the_number_core::storage;
+
+// The size of the EEPROM of the ATmega8.
+const CAPACITY: usize = 512;
+
+// EEPROM kept in a file, so the data survives restarts of the simulator.
+// A missing or broken file is an erased EEPROM.
+pub struct FileEeprom {
+    path: PathBuf,
+    data: Vec<u8>,
+}
+
+impl FileEeprom {
+    pub fn open(path: PathBuf) -> FileEeprom {
+        let mut data = fs::read(&path).unwrap_or_default();
+        data.resize(CAPACITY, 0xFF);
+
+        FileEeprom {
+            path,
+            data,
+        }
+    }
+}
+
+impl storage::Eeprom for FileEeprom {
+    fn capacity(&self) -> u16 {
+        CAPACITY as u16
+    }
+
+    fn read(&self, offset: u16, buffer: &mut [u8]) {
+        let offset = usize::from(offset);
+        buffer.copy_from_slice(&self.data[offset..offset + buffer.len()]);
+    }
+
+    fn write(&mut self, offset: u16, data: &[u8]) {
+        let offset = usize::from(offset);
+        self.data[offset..offset + data.len()].copy_from_slice(data);
+
+        // the simulator keeps running without persistence
+        let _ = fs::write(&self.path, &self.data);
+    }
+}
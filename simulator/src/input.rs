@@ -0,0 +1,123 @@
+use std::cell::Cell;
+use std::io::Read;
+use std::process::Command;
+use std::rc::Rc;
+use std::sync::mpsc;
+use std::thread;
+
+use guess_the_number_core::{button, hal};
+
+// How many main loop iterations a key press holds the button down.
+// Has to be longer than the debounce in button.rs, and longer than
+// the key repeat of the terminals, so a held key holds the button.
+const KEY_HOLD_ITERATIONS: u16 = 400;
+// Terminals do not report holding a key, long presses have their own keys.
+// Has to be longer than a long click in button.rs.
+const KEY_LONG_HOLD_ITERATIONS: u16 = button::PRESSED_FOR_LONG + 500;
+
+// A pull up input, so the pin is low while the button is held.
+#[derive(Clone)]
+pub struct SimInput {
+    held_for: Rc<Cell<u16>>,
+}
+
+impl SimInput {
+    pub fn create() -> SimInput {
+        SimInput {
+            held_for: Rc::new(Cell::new(0)),
+        }
+    }
+
+    #[inline]
+    pub fn press(&self) {
+        self.held_for.set(KEY_HOLD_ITERATIONS);
+    }
+
+    #[inline]
+    pub fn long_press(&self) {
+        self.held_for.set(KEY_LONG_HOLD_ITERATIONS);
+    }
+
+    // Called once per main loop iteration.
+    pub fn step(&self) {
+        let held_for = self.held_for.get();
+        if held_for > 0 {
+            self.held_for.set(held_for - 1);
+        }
+    }
+}
+
+impl hal::InputPin for SimInput {
+    #[inline]
+    fn is_low(&self) -> bool {
+        self.held_for.get() > 0
+    }
+}
+
+pub enum Key {
+    Digit(usize),
+    Confirm,
+    LongConfirm,
+    Quit,
+}
+
+impl Key {
+    fn from_byte(byte: u8) -> Option<Key> {
+        match byte {
+            b'1'..=b'4' => Some(Key::Digit(usize::from(byte - b'1'))),
+            b' ' | b'\n' | b'\r' => Some(Key::Confirm),
+            b'l' | b'L' => Some(Key::LongConfirm),
+            b'q' | b'Q' | 0x03 | 0x04 => Some(Key::Quit),
+            _ => None,
+        }
+    }
+}
+
+// Switches the terminal to unbuffered input without echo
+// and restores the original settings once dropped.
+pub struct Terminal {
+    saved: Option<String>,
+}
+
+impl Terminal {
+    pub fn setup() -> Terminal {
+        let saved = Command::new("stty")
+            .arg("-g")
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+
+        let _ = Command::new("stty").args(["-icanon", "-echo", "-isig", "min", "1"]).status();
+
+        Terminal { saved }
+    }
+
+    // Reads keys on a separate thread, so the game loop never blocks.
+    pub fn keys(&self) -> mpsc::Receiver<Key> {
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            let mut stdin = std::io::stdin();
+            let mut byte = [0u8; 1];
+
+            while let Ok(1) = stdin.read(&mut byte) {
+                if let Some(key) = Key::from_byte(byte[0]) {
+                    if sender.send(key).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        receiver
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        if let Some(saved) = &self.saved {
+            let _ = Command::new("stty").arg(saved).status();
+        }
+    }
+}
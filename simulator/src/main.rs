@@ -0,0 +1,157 @@
+// Host simulator of the game.
+// Runs the same game loop as the firmware,
+// with the displays rendered in the terminal
+// and the buttons mapped to keys.
+
+mod clock;
+mod display;
+mod eeprom;
+mod input;
+
+use guess_the_number_core::{animation, button, game, rng, storage};
+use guess_the_number_core::rng::Rng;
+
+use std::env;
+use std::io::Write;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+// Main loop iterations per second, roughly what the board does.
+const DEFAULT_ITERATIONS_PER_SECOND: u32 = 10000;
+const FRAMES_PER_SECOND: u32 = 30;
+const EEPROM_FILE: &str = "guess-the-number.eeprom";
+// Stands in for the button combination held at boot.
+const RESET_SCORES_ARG: &str = "--reset-scores";
+const SCORES_RESET_TEXT: &str = "scores reset";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let reset_scores = args.iter().any(|arg| arg == RESET_SCORES_ARG);
+    let iterations_per_second = args
+        .iter()
+        .find_map(|arg| arg.parse().ok())
+        .unwrap_or(DEFAULT_ITERATIONS_PER_SECOND);
+    let iterations_per_frame = (iterations_per_second / FRAMES_PER_SECOND).max(1);
+
+    let inputs = [
+        input::SimInput::create(),
+        input::SimInput::create(),
+        input::SimInput::create(),
+        input::SimInput::create(),
+    ];
+    let in_confirm = input::SimInput::create();
+
+    let buttons = [
+        button::Button::create(inputs[0].clone(), false),
+        button::Button::create(inputs[1].clone(), false),
+        button::Button::create(inputs[2].clone(), false),
+        button::Button::create(inputs[3].clone(), false),
+    ];
+    let btn_confirm = button::Button::create(in_confirm.clone(), false);
+
+    let mut storage = storage::Storage::create(eeprom::FileEeprom::open(EEPROM_FILE.into()));
+    let mut record = storage.load();
+
+    // the time stands in for the hardware entropy
+    let entropy = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.subsec_nanos().to_le_bytes())
+        .unwrap_or([0; 4]);
+    let mut rng = rng::XorShift32::init(record.seed);
+    rng.mix(entropy);
+
+    record.seed = rng.next_seed();
+    storage.save(&record);
+
+    let mut game = game::Game::create(
+        display::SevenSegmentFrame::create(),
+        display::LEDMatrixFrame::create(),
+        buttons,
+        btn_confirm,
+        rng,
+        clock::SimClock::create()
+    );
+    game.load(&record);
+    if reset_scores {
+        game.reset_high_scores();
+        game.show_text(&SCORES_RESET_TEXT, animation::MARQUEE_STEPS_PER_SHIFT);
+    } else {
+        game.set_animation(animation::AnimationKind::Hello);
+    }
+
+    let terminal = input::Terminal::setup();
+    let keys = terminal.keys();
+    let frame_duration = Duration::from_secs(1) / FRAMES_PER_SECOND;
+
+    let started = Instant::now();
+    print!("\x1b[2J");
+    loop {
+        let frame_start = Instant::now();
+
+        for key in keys.try_iter() {
+            match key {
+                input::Key::Digit(i) => inputs[i].press(),
+                input::Key::Confirm => in_confirm.press(),
+                input::Key::LongConfirm => in_confirm.long_press(),
+                input::Key::Quit => return,
+            }
+        }
+
+        for _ in 0..iterations_per_frame {
+            for input in inputs.iter() {
+                input.step();
+            }
+            in_confirm.step();
+
+            game.step_buttons();
+            game.step_animation();
+            game.step();
+
+            if game.take_save_request() {
+                game.save(&mut record);
+                storage.save(&record);
+            }
+        }
+
+        render(&game, started.elapsed().as_millis() as u32);
+
+        if let Some(remaining) = frame_duration.checked_sub(frame_start.elapsed()) {
+            thread::sleep(remaining);
+        }
+    }
+}
+
+type SimGame = game::Game<
+    display::SevenSegmentFrame,
+    display::LEDMatrixFrame,
+    input::SimInput,
+    rng::XorShift32,
+    clock::SimClock,
+    { display::DIGITS },
+>;
+
+fn render(game: &SimGame, millis: u32) {
+    let mut out = String::from("\x1b[H");
+
+    let (seven_segment, led_matrix) = game.displays();
+    for line in display::render_seven_segment(seven_segment).iter() {
+        out.push_str(line);
+        out.push('\n');
+    }
+    out.push('\n');
+    for line in display::render_led_matrix(led_matrix, millis).iter() {
+        out.push_str(line);
+        out.push('\n');
+    }
+    out.push_str(&format!(
+        "\n{:?}, attempts: {}, time: {:.1} s\x1b[K\n",
+        game.state(),
+        game.attempts(),
+        f64::from(game.elapsed()) / 1000.0
+    ));
+    out.push_str("\n[1-4] digit buttons  [space/enter] confirm  [l] long confirm  [q] quit\n");
+
+    let mut stdout = std::io::stdout();
+    let _ = stdout.write_all(out.as_bytes());
+    let _ = stdout.flush();
+}